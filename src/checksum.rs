@@ -0,0 +1,387 @@
+use crate::github::release::Asset;
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt::Formatter;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+/// Expected digest of an asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checksum {
+    pub algorithm: Algorithm,
+    pub hex: String,
+}
+
+/// Digests computed while downloading an asset
+#[derive(Debug, PartialEq)]
+pub struct Digests {
+    pub sha256: String,
+    pub sha512: String,
+}
+
+/// Computes the digests of a stream of bytes
+pub struct Hasher {
+    sha256: Sha256,
+    sha512: Sha512,
+}
+
+impl Algorithm {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn from_hex_length(length: usize) -> Option<Algorithm> {
+        match length {
+            64 => Some(Algorithm::Sha256),
+            128 => Some(Algorithm::Sha512),
+            _ => None,
+        }
+    }
+}
+
+impl Checksum {
+    /// Parse `<algorithm>:<hex>` (e.g. `sha256:e3b0c4...`) or a bare hex digest
+    pub fn try_parse(src: &str) -> Result<Checksum, String> {
+        let (algorithm, hex) = match src.split_once(':') {
+            Some(("sha256", hex)) => (Some(Algorithm::Sha256), hex),
+            Some(("sha512", hex)) => (Some(Algorithm::Sha512), hex),
+            Some((algorithm, _)) => {
+                return Err(format!(
+                    "Unsupported checksum algorithm '{}'. Use sha256 or sha512",
+                    algorithm
+                ));
+            }
+            None => (None, src),
+        };
+        let hex = hex.trim().to_lowercase();
+        if !is_hex(&hex) {
+            return Err("Invalid checksum. Digest must be hex encoded".to_string());
+        }
+
+        let expected_algorithm = Algorithm::from_hex_length(hex.len());
+        match (algorithm, expected_algorithm) {
+            (Some(x), Some(y)) if x == y => Ok(Checksum { algorithm: x, hex }),
+            (None, Some(x)) => Ok(Checksum { algorithm: x, hex }),
+            _ => Err(format!(
+                "Invalid checksum length ({} characters)",
+                hex.len()
+            )),
+        }
+    }
+
+    pub fn verify(&self, asset_name: &str, digests: &Digests) -> Result<(), ChecksumMismatch> {
+        let actual = digests.get(self.algorithm);
+        if self.hex == *actual {
+            return Ok(());
+        }
+
+        Err(ChecksumMismatch {
+            asset: asset_name.to_string(),
+            expected: self.clone(),
+            actual: Checksum {
+                algorithm: self.algorithm,
+                hex: actual.clone(),
+            },
+        })
+    }
+}
+
+impl std::fmt::Display for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.algorithm.as_str(), self.hex)
+    }
+}
+
+/// The downloaded bytes don't match the expected checksum (e.g. tampered or truncated download)
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    pub asset: String,
+    pub expected: Checksum,
+    pub actual: Checksum,
+}
+
+impl std::fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch for {}\nExpected: {}\nActual:   {}",
+            self.asset, self.expected, self.actual
+        )
+    }
+}
+
+impl Digests {
+    pub fn get(&self, algorithm: Algorithm) -> &String {
+        match algorithm {
+            Algorithm::Sha256 => &self.sha256,
+            Algorithm::Sha512 => &self.sha512,
+        }
+    }
+}
+
+impl Hasher {
+    pub fn new() -> Self {
+        Self {
+            sha256: Sha256::new(),
+            sha512: Sha512::new(),
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sha256.update(bytes);
+        self.sha512.update(bytes);
+    }
+
+    pub fn finalize(self) -> Digests {
+        Digests {
+            sha256: to_hex(&self.sha256.finalize()),
+            sha512: to_hex(&self.sha512.finalize()),
+        }
+    }
+}
+
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+const ASSET_CHECKSUM_EXTENSIONS: [&str; 4] = [".sha256", ".sha256sum", ".sha512", ".sha512sum"];
+const AGGREGATED_CHECKSUMS_NAMES: [&str; 3] = ["checksums", "sha256sums", "sha512sums"];
+const AGGREGATED_CHECKSUMS_EXTENSIONS: [&str; 2] = ["", ".txt"];
+
+/// Find the checksum file of an asset in the same release.
+/// Per-asset files (e.g. `<asset>.sha256`) have priority over aggregated ones (e.g. `checksums.txt`, `SHA256SUMS`).
+pub fn find_checksum_asset<'a>(asset_name: &str, assets: &'a [Asset]) -> Option<&'a Asset> {
+    let per_asset = ASSET_CHECKSUM_EXTENSIONS.iter().find_map(|extension| {
+        let name = format!("{}{}", asset_name, extension).to_lowercase();
+        assets.iter().find(|x| x.name.to_lowercase() == name)
+    });
+
+    per_asset.or_else(|| assets.iter().find(|x| is_aggregated_checksums(&x.name)))
+}
+
+fn is_aggregated_checksums(name: &str) -> bool {
+    let name = name.to_lowercase();
+    AGGREGATED_CHECKSUMS_NAMES.iter().any(|checksums| {
+        AGGREGATED_CHECKSUMS_EXTENSIONS
+            .iter()
+            .any(|extension| name.ends_with(&format!("{}{}", checksums, extension)))
+    })
+}
+
+/// Find the checksum of an asset in a checksum file.
+///
+/// Supported formats are:
+/// - GNU coreutils (`<hex>  <file>` or `<hex> *<file>`)
+/// - BSD (`SHA256 (<file>) = <hex>`)
+/// - a single digest, without file name (e.g. per-asset checksum files)
+pub fn find_checksum_in(content: &str, asset_name: &str) -> Option<Checksum> {
+    let lines: Vec<_> = content
+        .lines()
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .collect();
+
+    let named = lines.iter().find_map(|line| {
+        let (hex, file) = parse_line(line)?;
+        if file? == asset_name {
+            Checksum::try_parse(hex).ok()
+        } else {
+            None
+        }
+    });
+
+    named.or_else(|| match lines.as_slice() {
+        [line] => parse_line(line)
+            .filter(|(_, file)| file.is_none())
+            .and_then(|(hex, _)| Checksum::try_parse(hex).ok()),
+        _ => None,
+    })
+}
+
+fn parse_line(line: &str) -> Option<(&str, Option<&str>)> {
+    if let Some((left, hex)) = line.split_once(") = ") {
+        let (_, file) = left.split_once(" (")?;
+        return Some((hex.trim(), Some(file)));
+    }
+
+    let mut parts = line.splitn(2, char::is_whitespace);
+    let hex = parts.next()?;
+    let file = parts
+        .next()
+        .map(|x| x.trim_start().trim_start_matches('*'))
+        .map(|x| x.strip_prefix("./").unwrap_or(x))
+        .filter(|x| !x.is_empty());
+    Some((hex, file))
+}
+
+fn is_hex(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|x| x.is_ascii_hexdigit())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|x| format!("{:02x}", x)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const HELLO_WORLD_SHA256: &str =
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+    const ANY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn digests() {
+        let mut hasher = Hasher::new();
+        hasher.update(b"hello ");
+        hasher.update(b"world");
+
+        let result = hasher.finalize();
+
+        assert_eq!(HELLO_WORLD_SHA256, result.sha256);
+        assert_eq!(128, result.sha512.len());
+    }
+
+    #[test_case(&format!("sha256:{}", HELLO_WORLD_SHA256); "with algorithm")]
+    #[test_case(HELLO_WORLD_SHA256; "bare hex")]
+    #[test_case(&HELLO_WORLD_SHA256.to_uppercase(); "uppercase hex")]
+    fn parse_checksum(value: &str) {
+        let result = Checksum::try_parse(value);
+
+        assert_eq!(
+            Ok(Checksum {
+                algorithm: Algorithm::Sha256,
+                hex: HELLO_WORLD_SHA256.to_string()
+            }),
+            result
+        );
+    }
+
+    #[test_case("md5:abcd"; "unsupported algorithm")]
+    #[test_case("sha256:xyz"; "not hex")]
+    #[test_case("sha512:abcd"; "wrong length")]
+    #[test_case(&format!("sha512:{}", HELLO_WORLD_SHA256); "length of another algorithm")]
+    fn invalid_checksum(value: &str) {
+        let result = Checksum::try_parse(value);
+
+        assert!(result.is_err(), "Expected Err, got {:?}", result);
+    }
+
+    #[test]
+    fn verify_matching_checksum() {
+        let checksum = Checksum::try_parse(HELLO_WORLD_SHA256).unwrap();
+
+        let result = checksum.verify("tool.tar.gz", &hello_world_digests());
+
+        assert!(result.is_ok(), "Expected Ok, got {:?}", result);
+    }
+
+    #[test]
+    fn verify_checksum_mismatch() {
+        let checksum = Checksum::try_parse(ANY_SHA256).unwrap();
+
+        let result = checksum.verify("tool.tar.gz", &hello_world_digests());
+
+        match result {
+            Ok(_) => panic!("Expected Err, got Ok"),
+            Err(e) => {
+                assert_eq!("tool.tar.gz", e.asset);
+                assert_eq!(checksum, e.expected);
+                assert_eq!(HELLO_WORLD_SHA256, e.actual.hex);
+            }
+        }
+    }
+
+    #[test]
+    fn per_asset_checksum_file_has_priority() {
+        let assets = vec![
+            asset("checksums.txt"),
+            asset("tool.tar.gz"),
+            asset("tool.tar.gz.sha256"),
+        ];
+
+        let result = find_checksum_asset("tool.tar.gz", &assets);
+
+        assert_eq!(Some("tool.tar.gz.sha256"), result.map(|x| x.name.as_str()));
+    }
+
+    #[test_case("checksums.txt")]
+    #[test_case("tool_1.0.0_checksums.txt")]
+    #[test_case("SHA256SUMS")]
+    #[test_case("sha512sums.txt")]
+    fn aggregated_checksum_file(name: &str) {
+        let assets = vec![
+            asset("tool.tar.gz"),
+            asset(&format!("{}.sig", name)),
+            asset(name),
+        ];
+
+        let result = find_checksum_asset("tool.tar.gz", &assets);
+
+        assert_eq!(Some(name), result.map(|x| x.name.as_str()));
+    }
+
+    #[test]
+    fn no_checksum_file() {
+        let assets = vec![asset("tool.tar.gz"), asset("other.tar.gz.sha256")];
+
+        let result = find_checksum_asset("tool.tar.gz", &assets);
+
+        assert!(result.is_none());
+    }
+
+    #[test_case(&format!("{}  other.tar.gz\n{}  tool.tar.gz\n", ANY_SHA256, HELLO_WORLD_SHA256); "gnu")]
+    #[test_case(&format!("{} *tool.tar.gz", HELLO_WORLD_SHA256); "gnu binary mode")]
+    #[test_case(&format!("{}  ./tool.tar.gz", HELLO_WORLD_SHA256); "relative path")]
+    #[test_case(&format!("SHA256 (other.tar.gz) = {}\nSHA256 (tool.tar.gz) = {}", ANY_SHA256, HELLO_WORLD_SHA256); "bsd")]
+    #[test_case(&format!("{}\n", HELLO_WORLD_SHA256); "only digest")]
+    fn checksum_in_file(content: &str) {
+        let result = find_checksum_in(content, "tool.tar.gz");
+
+        assert_eq!(
+            Some(HELLO_WORLD_SHA256),
+            result.as_ref().map(|x| x.hex.as_str())
+        );
+    }
+
+    #[test]
+    fn asset_not_in_checksum_file() {
+        let content = format!(
+            "{}  other.tar.gz\n{}  another.tar.gz",
+            ANY_SHA256, ANY_SHA256
+        );
+
+        let result = find_checksum_in(&content, "tool.tar.gz");
+
+        assert!(result.is_none());
+    }
+
+    fn hello_world_digests() -> Digests {
+        let mut hasher = Hasher::new();
+        hasher.update(b"hello world");
+        hasher.finalize()
+    }
+
+    fn asset(name: &str) -> Asset {
+        Asset {
+            name: name.to_string(),
+            display_name: None,
+            download_url: "ANY_DOWNLOAD_URL".to_string(),
+            digest: None,
+        }
+    }
+}
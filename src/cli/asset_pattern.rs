@@ -0,0 +1,140 @@
+use crate::github::release::{Asset, Release, Tag};
+use crate::github::tagged_asset::TaggedAsset;
+use regex::Regex;
+
+/// Pattern used to select assets, `{tag}` is replaced with the release version before matching
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetPattern {
+    /// Literal, untagged or wildcard pattern (e.g. helloworld_{tag}_*.tar.gz)
+    Wildcard(String),
+    /// Regular expression that matches any part of the asset name (e.g. linux.*x86_64)
+    Regex(String),
+}
+
+pub enum AssetMatcher {
+    Wildcard(wildmatch::WildMatch),
+    Regex(Regex),
+}
+
+impl AssetPattern {
+    /// Check the regular expression is valid, so errors are reported before fetching the release
+    pub fn try_parse_regex(src: &str) -> Result<String, String> {
+        Regex::new(&TaggedAsset::tag_regex(&Tag("0.0.0".to_string()), src))
+            .map(|_| src.to_string())
+            .map_err(|e| format!("Invalid regular expression: {}", e))
+    }
+
+    /// Assets of the release matching the pattern, in the same order of the release
+    pub fn matching_assets<'a>(&self, release: &'a Release) -> Result<Vec<&'a Asset>, String> {
+        let matcher = self.matcher(&release.tag)?;
+        Ok(release
+            .assets
+            .iter()
+            .filter(|x| matcher.matches(&x.name))
+            .collect())
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AssetPattern::Regex(_) => "regex",
+            AssetPattern::Wildcard(x) => {
+                let is_untagged = x.contains(TaggedAsset::PLACEHOLDER);
+                let is_wildcard = x.contains(['*', '?']);
+                match (is_untagged, is_wildcard) {
+                    (true, true) => "untagged wildcard",
+                    (true, false) => "untagged",
+                    (false, true) => "wildcard",
+                    (false, false) => "literal",
+                }
+            }
+        }
+    }
+
+    /// Pattern with `{tag}` replaced by the release version
+    pub fn tagged(&self, tag: &Tag) -> String {
+        match self {
+            AssetPattern::Wildcard(x) => TaggedAsset::tag(tag, x),
+            AssetPattern::Regex(x) => TaggedAsset::tag_regex(tag, x),
+        }
+    }
+
+    pub fn matcher(&self, tag: &Tag) -> Result<AssetMatcher, String> {
+        match self {
+            AssetPattern::Wildcard(pattern) => Ok(AssetMatcher::Wildcard(
+                wildmatch::WildMatch::new(&TaggedAsset::tag(tag, pattern)),
+            )),
+            AssetPattern::Regex(pattern) => Regex::new(&TaggedAsset::tag_regex(tag, pattern))
+                .map(AssetMatcher::Regex)
+                .map_err(|e| format!("Invalid regular expression {}: {}", pattern, e)),
+        }
+    }
+}
+
+/// Remove the assets matching any of the exclusion patterns
+pub fn exclude_assets(release: Release, exclude: &[String]) -> Release {
+    let patterns = exclude
+        .iter()
+        .map(|x| wildmatch::WildMatch::new(&TaggedAsset::tag(&release.tag, x)))
+        .collect::<Vec<_>>();
+    Release {
+        assets: release
+            .assets
+            .into_iter()
+            .filter(|asset| !patterns.iter().any(|x| x.matches(&asset.name)))
+            .collect(),
+        ..release
+    }
+}
+
+impl AssetMatcher {
+    pub fn matches(&self, asset_name: &str) -> bool {
+        match self {
+            AssetMatcher::Wildcard(x) => x.matches(asset_name),
+            AssetMatcher::Regex(x) => x.is_match(asset_name),
+        }
+    }
+}
+
+impl std::fmt::Display for AssetPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetPattern::Wildcard(x) => write!(f, "{}", x),
+            AssetPattern::Regex(x) => write!(f, "/{}/", x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(AssetPattern::Wildcard("tool-{tag}-*.tar.gz".into()), "tool-1.2.0-linux.tar.gz", true; "wildcard")]
+    #[test_case(AssetPattern::Wildcard("tool-*.tar.gz".into()), "tool-linux.tar.gz.sha256", false; "wildcard whole name")]
+    #[test_case(AssetPattern::Regex("linux.*x86_64".into()), "tool-linux-x86_64.tar.gz", true; "regex")]
+    #[test_case(AssetPattern::Regex("^tool-{tag}-".into()), "tool-1.2.0-linux.tar.gz", true; "regex with tag")]
+    #[test_case(AssetPattern::Regex("^tool-{tag}-".into()), "tool-1x2x0-linux.tar.gz", false; "regex escapes tag")]
+    #[test_case(AssetPattern::Regex("linux.*x86_64$".into()), "tool-linux-x86_64.sha256", false; "regex anchored")]
+    fn pattern_matches(pattern: AssetPattern, asset_name: &str, expected: bool) {
+        let matcher = pattern.matcher(&Tag("v1.2.0".into())).unwrap();
+
+        assert_eq!(expected, matcher.matches(asset_name));
+    }
+
+    #[test_case(AssetPattern::Wildcard("tool.tar.gz".into()), "literal"; "literal")]
+    #[test_case(AssetPattern::Wildcard("tool-{tag}.tar.gz".into()), "untagged"; "untagged")]
+    #[test_case(AssetPattern::Wildcard("tool-*.tar.gz".into()), "wildcard"; "wildcard")]
+    #[test_case(AssetPattern::Wildcard("tool-{tag}-*.tar.gz".into()), "untagged wildcard"; "untagged wildcard")]
+    #[test_case(AssetPattern::Regex("tool".into()), "regex"; "regex")]
+    fn pattern_kind(pattern: AssetPattern, expected: &str) {
+        assert_eq!(expected, pattern.kind());
+    }
+
+    #[test_case("linux.*x86_64", true; "valid")]
+    #[test_case("tool-{tag}-(gnu|musl", false; "invalid")]
+    fn parse_regex(src: &str, is_valid: bool) {
+        let result = AssetPattern::try_parse_regex(src);
+
+        assert_eq!(is_valid, result.is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::checksum::{Checksum, Digests, find_checksum_asset, find_checksum_in};
+use crate::cli::color::Color;
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
+use crate::cli::result::{ErrorKind, HandlerError};
+use crate::github::client::GithubClient;
+use crate::github::release::Asset;
+use std::io::Read;
+use std::path::Path;
+
+// NOTE: checksum files are small, this avoids reading a wrong asset entirely in memory
+const MAX_CHECKSUM_FILE_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    Disabled,
+    /// Find the checksum of the asset in the release checksum files
+    ChecksumAsset,
+    Checksum(Checksum),
+}
+
+impl Verification {
+    pub fn new(verify: bool, checksum: Option<Checksum>) -> Self {
+        match (checksum, verify) {
+            (Some(x), _) => Self::Checksum(x),
+            (None, true) => Self::ChecksumAsset,
+            (None, false) => Self::Disabled,
+        }
+    }
+}
+
+pub fn expected_checksum(
+    github: &GithubClient,
+    verification: &Verification,
+    asset: &Asset,
+    release_assets: &[Asset],
+) -> Result<Option<Checksum>, HandlerError> {
+    match verification {
+        Verification::Disabled => Ok(None),
+        Verification::Checksum(x) => Ok(Some(x.clone())),
+        Verification::ChecksumAsset => {
+            let checksum_asset =
+                find_checksum_asset(&asset.name, release_assets).ok_or_else(|| {
+                    HandlerError::with_kind(ErrorKind::NotFound, format!(
+                        "No checksum file found for {} in this release. Use --checksum <CHECKSUM> to provide one",
+                        asset.name
+                    ))
+                })?;
+            let content = download_checksum_file(github, checksum_asset)?;
+            find_checksum_in(&content, &asset.name)
+                .ok_or_else(|| {
+                    HandlerError::with_kind(
+                        ErrorKind::NotFound,
+                        format!(
+                            "No checksum found for {} in {}",
+                            asset.name, checksum_asset.name
+                        ),
+                    )
+                })
+                .map(Some)
+        }
+    }
+}
+
+fn download_checksum_file(github: &GithubClient, asset: &Asset) -> Result<String, HandlerError> {
+    let (stream, _) = github.download_asset_stream(asset).map_err(|e| {
+        HandlerError::with_kind(
+            ErrorKind::from(&e),
+            format!("Error downloading checksum file {}: {}", asset.name, e),
+        )
+    })?;
+
+    let mut content = String::new();
+    stream
+        .take(MAX_CHECKSUM_FILE_SIZE)
+        .read_to_string(&mut content)
+        .map_err(|e| {
+            HandlerError::new(format!("Error reading checksum file {}: {}", asset.name, e))
+        })?;
+    Ok(content)
+}
+
+/// The downloaded file is deleted when the checksum doesn't match, so the download starts over next time
+pub fn verify_checksum(
+    asset: &Asset,
+    path: &Path,
+    expected: Option<&Checksum>,
+    digests: &Digests,
+) -> Result<(), HandlerError> {
+    let expected = match expected {
+        Some(x) => x,
+        None => return Ok(()),
+    };
+
+    verify_or_delete(expected, asset, path, digests)?;
+    progress::println(&format!(
+        "{}",
+        Color::new(&format!(
+            "Checksum verified ({})",
+            expected.algorithm.as_str()
+        ))
+        .green()
+    ));
+    output::emit(Event::ChecksumVerified {
+        asset: &asset.name,
+        algorithm: expected.algorithm.as_str(),
+    });
+    Ok(())
+}
+
+/// Verify the digest published by GitHub for the asset, if any.
+/// The downloaded file is deleted when the digest doesn't match
+pub fn verify_asset_digest(
+    asset: &Asset,
+    path: &Path,
+    digests: &Digests,
+) -> Result<(), HandlerError> {
+    match &asset.digest {
+        Some(digest) => verify_or_delete(digest, asset, path, digests),
+        None => Ok(()),
+    }
+}
+
+fn verify_or_delete(
+    expected: &Checksum,
+    asset: &Asset,
+    path: &Path,
+    digests: &Digests,
+) -> Result<(), HandlerError> {
+    expected.verify(&asset.name, digests).map_err(|e| {
+        let _ = std::fs::remove_file(path);
+        HandlerError::ChecksumMismatch(e)
+    })
+}
@@ -1,46 +1,91 @@
+use crate::checksum::{Checksum, Digests};
+use crate::cli::asset_pattern::{AssetPattern, exclude_assets};
+use crate::cli::checksum_verification::{
+    Verification, expected_checksum, verify_asset_digest, verify_checksum,
+};
 use crate::cli::color::Color;
 use crate::cli::github_release::fetch_release_for;
+use crate::cli::output::{self, Event, ExecutableFailure, PlannedExecutable};
+use crate::cli::progress;
 use crate::cli::progress_bar::ProgressBar;
-use crate::cli::result::{HandlerError, HandlerResult};
+use crate::cli::release_channel::LatestRelease;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
 use crate::cli::select_assets;
+use crate::cli::signature_verification::verify_signature;
 use crate::cli::spinner::Spinner;
-use crate::github::client::GithubClient;
+use crate::cli::tag_requirement::TagRequirement;
+use crate::cli::worker_pool;
+use crate::github::client::{ClientOptions, GithubClient};
 use crate::github::error::GithubError;
 use crate::github::release::{Asset, Release, Tag};
 use crate::github::repository::Repository;
 use crate::github::tagged_asset::TaggedAsset;
+use crate::installer;
 use crate::installer::destination::Destination;
+use crate::installer::error::InstallError;
 use crate::installer::executable::Executable;
-use crate::installer::install;
-use crate::{system, vector};
-use std::fs::File;
-use std::io::{Read, Write};
+use crate::installer::{InstallPlan, InstallTarget, install};
+use crate::signature::PublicKey;
+use crate::state::{
+    InstallState, InstalledSelection, InstalledSignature, InstalledTool, InstalledVerification,
+};
+use crate::{checksum, state, system, vector};
+use itertools::{Either, Itertools};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 
+// NOTE: number of times an interrupted download is resumed before giving up
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+pub const DEFAULT_JOBS: usize = 4;
+const DOWNLOAD_BUFFER_SIZE: usize = 64 * 1024;
+
 pub struct DownloadHandler {
     repository: Repository,
     mode: DownloadMode,
+    /// Patterns of assets that are never selected
+    exclude: Vec<String>,
+    /// Fail when a pattern matches more than one asset
+    strict: bool,
+    /// Select more than one asset in interactive mode
+    multiple: bool,
     tag: Option<Tag>,
+    latest: LatestRelease,
     output: Option<PathBuf>,
     install: Install,
+    verification: Verification,
+    signature_key: Option<PublicKey>,
+    client_options: ClientOptions,
+    /// Maximum number of assets downloaded at the same time
+    jobs: usize,
+    /// Show what would be downloaded and installed without writing to the destination
+    dry_run: bool,
 }
 
 enum DownloadMode {
     Interactive,
-    Selection(String),
+    /// One asset is downloaded for each pattern
+    Selection(Vec<AssetPattern>),
     Automatic,
 }
 
 impl DownloadMode {
-    fn new(select: Option<String>, automatic: bool) -> Self {
-        match (select, automatic) {
-            (Some(x), _) => Self::Selection(x),
-            (_, true) => Self::Automatic,
-            (None, false) => Self::Interactive,
+    fn new(select: Vec<AssetPattern>, automatic: bool) -> Self {
+        match (select.is_empty(), automatic) {
+            (false, _) => Self::Selection(select),
+            (true, true) => Self::Automatic,
+            (true, false) => Self::Interactive,
         }
     }
 }
 
+enum DownloadError {
+    /// The connection was interrupted, the download can be resumed
+    Interrupted(std::io::Error),
+    Failed(HandlerError),
+}
+
 enum Install {
     No,
     Yes(Vec<Executable>),
@@ -78,7 +123,7 @@ impl Install {
 impl DownloadHandler {
     pub fn new(
         repository: Repository,
-        select: Option<String>,
+        select: Vec<AssetPattern>,
         automatic: bool,
         tag: Option<String>,
         output: Option<PathBuf>,
@@ -88,106 +133,452 @@ impl DownloadHandler {
         let install = Install::new(install, install_file, &repository);
         DownloadHandler {
             repository,
-            mode: DownloadMode::new(select.clone(), automatic),
+            mode: DownloadMode::new(select, automatic),
+            exclude: vec![],
+            strict: false,
+            multiple: false,
             tag: tag.map(Tag),
+            latest: LatestRelease::default(),
             output,
             install,
+            verification: Verification::Disabled,
+            signature_key: None,
+            client_options: ClientOptions::new(None, None, false, None),
+            jobs: DEFAULT_JOBS,
+            dry_run: false,
         }
     }
 
+    pub fn with_verification(mut self, verify: bool, checksum: Option<Checksum>) -> Self {
+        self.verification = Verification::new(verify, checksum);
+        self
+    }
+
+    pub fn with_latest_release(mut self, latest: LatestRelease) -> Self {
+        self.latest = latest;
+        self
+    }
+
+    pub fn with_signature_key(mut self, key: Option<PublicKey>) -> Self {
+        self.signature_key = key;
+        self
+    }
+
+    pub fn with_exclude(mut self, exclude: Vec<String>) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn with_multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn with_client_options(mut self, client_options: ClientOptions) -> Self {
+        self.client_options = client_options;
+        self
+    }
+
     pub fn run(&self) -> HandlerResult {
-        let github = GithubClient::from_environment();
-        let release = self.fetch_release(&github)?;
-        let selected_asset = self.select_asset(release)?;
-        let output_path = self.choose_output_path(&selected_asset.name);
-        Self::download_asset(&github, &selected_asset, &output_path)?;
-        self.maybe_install(&selected_asset.name, &output_path)?;
+        let github = GithubClient::from_environment(&self.client_options);
+        self.run_with(&github)
+    }
+
+    pub fn run_with(&self, github: &GithubClient) -> HandlerResult {
+        let release = self.fetch_release(github)?;
+        self.run_for(github, release)
+    }
+
+    /// Download (and install) the selected assets of an already fetched release
+    pub fn run_for(&self, github: &GithubClient, release: Release) -> HandlerResult {
+        let tag = release.tag.clone();
+        let release_assets = release.assets.clone();
+        let selected_assets = self.select_assets(release)?;
+        for asset in &selected_assets {
+            output::emit(Event::AssetSelected {
+                repository: self.repository.to_string(),
+                asset: &asset.name,
+                download_url: &asset.download_url,
+            });
+        }
+        if self.dry_run {
+            return self.dry_run(github, &tag, &selected_assets, &release_assets);
+        }
+        match selected_assets.as_slice() {
+            [selected_asset] => {
+                let output_path = self.choose_output_path(&tag, &selected_asset.name);
+                let digests =
+                    self.download_verified(github, selected_asset, &release_assets, &output_path)?;
+                self.maybe_install(&tag, selected_asset, &output_path, digests.sha256)
+            }
+            _ => self.download_all(github, &tag, &selected_assets, &release_assets),
+        }
+    }
+
+    /// Download an asset and verify its checksum and signature
+    fn download_verified(
+        &self,
+        github: &GithubClient,
+        selected_asset: &Asset,
+        release_assets: &[Asset],
+        output_path: &Path,
+    ) -> Result<Digests, HandlerError> {
+        let checksum =
+            expected_checksum(github, &self.verification, selected_asset, release_assets)?;
+        Self::download_checked(github, selected_asset, output_path, |part_path, digests| {
+            verify_checksum(selected_asset, part_path, checksum.as_ref(), digests)?;
+            verify_signature(
+                github,
+                self.signature_key.as_ref(),
+                selected_asset,
+                release_assets,
+                part_path,
+            )
+        })
+    }
+
+    /// Download every asset into the output directory concurrently, even if some of them fail
+    fn download_all(
+        &self,
+        github: &GithubClient,
+        tag: &Tag,
+        selected_assets: &[Asset],
+        release_assets: &[Asset],
+    ) -> HandlerResult {
+        self.check_multiple_assets_invariants()?;
+
+        let results = worker_pool::run(selected_assets, self.jobs, |asset| {
+            let output_path = self.choose_output_path(tag, &asset.name);
+            let result = self
+                .download_verified(github, asset, release_assets, &output_path)
+                .map(|_| output_path);
+            if let Err(e) = &result {
+                progress::eprintln(&format!("{}", Color::new(&e.to_string()).red()));
+                output::emit(Event::DownloadFailed {
+                    asset: &asset.name,
+                    message: e.to_string(),
+                });
+            }
+            (asset, result)
+        });
+
+        progress::println(&format!("{}", Color::new("Download summary:").bold()));
+        for (asset, result) in &results {
+            match result {
+                Ok(path) => {
+                    progress::println(&format!("{} {}", Color::new("✔").green(), path.display()))
+                }
+                Err(e) => progress::println(&format!(
+                    "{} {}: {}",
+                    Color::new("✘").red(),
+                    asset.name,
+                    e.to_string().lines().next().unwrap_or_default()
+                )),
+            }
+        }
+
+        let failed = results
+            .iter()
+            .filter_map(|(_, x)| x.as_ref().err().map(HandlerError::kind))
+            .collect::<Vec<_>>();
+        if !failed.is_empty() {
+            return Err(HandlerError::with_kind(
+                ErrorKind::common(failed.iter().copied()),
+                format!(
+                    "{} of {} assets failed to download",
+                    failed.len(),
+                    results.len()
+                ),
+            ));
+        }
         Ok(())
     }
 
+    /// Show where the assets would be saved and, when installing, what the installer would do.
+    /// Only the asset to install is downloaded, to a temporary file
+    fn dry_run(
+        &self,
+        github: &GithubClient,
+        tag: &Tag,
+        selected_assets: &[Asset],
+        release_assets: &[Asset],
+    ) -> HandlerResult {
+        if selected_assets.len() > 1 {
+            self.check_multiple_assets_invariants()?;
+        }
+        progress::println(&format!(
+            "{}",
+            Color::new("Dry run, nothing is written to the destination").yellow()
+        ));
+
+        let executables = match &self.install {
+            Install::No => {
+                for asset in selected_assets {
+                    let output_path = self.choose_output_path(tag, &asset.name);
+                    progress::println(&format!(
+                        "Would download {} to {}",
+                        Color::new(&asset.name).bold(),
+                        output_path.display()
+                    ));
+                    output::emit(Event::DownloadPlanned {
+                        asset: &asset.name,
+                        path: &output_path,
+                    });
+                }
+                return Ok(());
+            }
+            Install::Yes(executables) => executables,
+        };
+
+        // NOTE: only one asset can be installed
+        let asset = &selected_assets[0];
+        let destination = self.destination(&cwd()?);
+        self.check_destination_invariants(&destination)?;
+        let path = self.choose_output_path(tag, &asset.name);
+        self.download_verified(github, asset, release_assets, &path)?;
+        let plan = installer::plan(&asset.name, &path, destination, executables.clone());
+        remove_temporary_file(&path)?;
+        let plan = plan.map_err(|e| install_error(asset, e))?;
+        show_install_plan(asset, &plan)
+    }
+
+    fn check_multiple_assets_invariants(&self) -> Result<(), HandlerError> {
+        if self.install.as_bool() {
+            return Err(HandlerError::with_kind(
+                ErrorKind::Usage,
+                "Only one asset can be installed at a time".into(),
+            ));
+        }
+        if let Verification::Checksum(_) = self.verification {
+            return Err(HandlerError::with_kind(
+                ErrorKind::Usage,
+                "--checksum can only be used when downloading one asset".into(),
+            ));
+        }
+        match &self.output {
+            Some(output) if !output.is_dir() => Err(HandlerError::with_kind(
+                ErrorKind::Usage,
+                format!(
+                    "{} is not a directory. When you download multiple assets, you must provide a directory path",
+                    output.display()
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
     fn fetch_release(&self, github: &GithubClient) -> Result<Release, HandlerError> {
-        fetch_release_for(github, &self.repository, self.tag.as_ref())
+        fetch_release_for(github, &self.repository, self.tag.as_ref(), self.latest)
     }
 
-    fn select_asset(&self, release: Release) -> Result<Asset, HandlerError> {
+    fn select_assets(&self, release: Release) -> Result<Vec<Asset>, HandlerError> {
+        let release = exclude_assets(release, &self.exclude);
         match &self.mode {
-            DownloadMode::Interactive => ask_select_asset(release.assets),
-            DownloadMode::Selection(selection) => autoselect_asset(release, selection),
+            DownloadMode::Interactive if self.multiple => ask_select_assets(release.assets),
+            DownloadMode::Interactive => ask_select_asset(release.assets).map(|x| vec![x]),
+            DownloadMode::Selection(selections) => {
+                autoselect_assets(&release, selections, self.strict)
+            }
             DownloadMode::Automatic => {
                 let system = system::from_environment().map_err(|e| {
                     automatic_download_system_error(&self.repository, &release.tag, e)
                 })?;
-                system::find_asset_by_system(&system, release.assets).ok_or_else(|| {
-                    automatic_download_error(&self.repository, &release.tag, &system)
-                })
+                system::find_asset_by_system(&system, release.assets)
+                    .map(|x| vec![x])
+                    .ok_or_else(|| {
+                        automatic_download_error(&self.repository, &release.tag, &system)
+                    })
             }
         }
     }
 
-    fn choose_output_path(&self, asset_name: &str) -> PathBuf {
-        choose_output_path_from(
-            self.output.as_ref(),
-            self.install.as_bool(),
-            asset_name,
-            Path::is_dir,
-        )
+    fn choose_output_path(&self, tag: &Tag, asset_name: &str) -> PathBuf {
+        let install_path = self
+            .install
+            .as_bool()
+            .then(|| install_temp_file(&self.repository, tag, asset_name));
+        choose_output_path_from(self.output.as_ref(), install_path, asset_name, Path::is_dir)
     }
 
-    fn download_asset(
+    pub fn download_asset(
         github: &GithubClient,
         selected_asset: &Asset,
         output_path: &Path,
-    ) -> Result<(), HandlerError> {
+    ) -> Result<Digests, HandlerError> {
+        Self::download_checked(github, selected_asset, output_path, |_, _| Ok(()))
+    }
+
+    /// Download the asset into the `.part` file and move it to the output path only when its digest
+    /// and `verify` succeed, so an asset that fails verification never replaces an existing file
+    fn download_checked<V>(
+        github: &GithubClient,
+        selected_asset: &Asset,
+        output_path: &Path,
+        verify: V,
+    ) -> Result<Digests, HandlerError>
+    where
+        V: FnOnce(&Path, &Digests) -> HandlerResult,
+    {
+        let part_path = part_file_path(output_path);
         let progress_bar = ProgressBar::download_layout(&selected_asset.name, output_path);
         progress_bar.show();
-        let (mut stream, maybe_content_length) = github
-            .download_asset_stream(selected_asset)
-            .map_err(download_asset_error)?;
-        progress_bar.set_length(maybe_content_length);
-
-        let mut destination = create_file(output_path)?;
-        let mut total_bytes = 0;
-        let mut buffer = [0; 1024];
-        while let Ok(bytes) = stream.read(&mut buffer) {
+
+        let mut resume_attempts = 0;
+        let digests = loop {
+            match Self::download_to_part_file(github, selected_asset, &part_path, &progress_bar) {
+                Ok(digests) => break digests,
+                Err(DownloadError::Interrupted(_)) if resume_attempts < MAX_RESUME_ATTEMPTS => {
+                    resume_attempts += 1;
+                    std::thread::sleep(github.retry_policy().delay(resume_attempts, None));
+                }
+                Err(DownloadError::Interrupted(e)) => {
+                    return Err(download_interrupted_error(&selected_asset.name, e));
+                }
+                Err(DownloadError::Failed(e)) => return Err(e),
+            }
+        };
+        progress_bar.finish();
+
+        let verified = verify_asset_digest(selected_asset, &part_path, &digests)
+            .and_then(|_| verify(&part_path, &digests));
+        if verified.is_err() {
+            // NOTE: the part file is deleted by the verification, so the download starts over next time
+            remove_resume_info(&part_path);
+        }
+        verified?;
+        std::fs::rename(&part_path, output_path)
+            .map_err(|x| save_to_file_error(&selected_asset.name, output_path, x))?;
+        remove_resume_info(&part_path);
+        output::emit(Event::DownloadFinished {
+            asset: &selected_asset.name,
+            path: output_path,
+            size: std::fs::metadata(output_path).map(|x| x.len()).unwrap_or(0),
+            sha256: &digests.sha256,
+        });
+        Ok(digests)
+    }
+
+    /// Download the asset into the `.part` file, resuming from its current size when the server supports it
+    /// and the `.part` file was downloaded from the same asset, which hasn't changed since then
+    fn download_to_part_file(
+        github: &GithubClient,
+        selected_asset: &Asset,
+        part_path: &Path,
+        progress_bar: &ProgressBar,
+    ) -> Result<Digests, DownloadError> {
+        let validator = read_resume_info(part_path)
+            .filter(|x| x.url == selected_asset.download_url)
+            .map(|x| x.validator);
+        let offset = match validator {
+            Some(_) => std::fs::metadata(part_path).map(|x| x.len()).unwrap_or(0),
+            None => 0,
+        };
+        let stream = github
+            .download_asset_stream_from(selected_asset, offset, validator.as_deref())
+            .map_err(|e| DownloadError::Failed(download_asset_error(e)))?;
+        progress_bar.set_length(stream.content_length);
+        progress_bar.update_progress(stream.offset);
+
+        let mut hasher = checksum::Hasher::new();
+        let file = if stream.offset > 0 {
+            open_part_file(part_path, &mut hasher)
+        } else {
+            create_file(part_path)
+        }
+        .map_err(DownloadError::Failed)?;
+        if stream.offset == 0 {
+            write_resume_info(part_path, selected_asset, stream.validator);
+        }
+        let mut destination = BufWriter::with_capacity(DOWNLOAD_BUFFER_SIZE, file);
+
+        let mut reader = stream.reader;
+        let mut total_bytes = stream.offset;
+        let mut buffer = vec![0; DOWNLOAD_BUFFER_SIZE];
+        loop {
+            let bytes = reader
+                .read(&mut buffer)
+                .map_err(DownloadError::Interrupted)?;
             if bytes == 0 {
                 break;
             }
 
-            destination
-                .write(&buffer[..bytes])
-                .map_err(|x| save_to_file_error(&selected_asset.name, output_path, x))?;
+            destination.write_all(&buffer[..bytes]).map_err(|x| {
+                DownloadError::Failed(save_to_file_error(&selected_asset.name, part_path, x))
+            })?;
+            hasher.update(&buffer[..bytes]);
 
             total_bytes += bytes as u64;
             progress_bar.update_progress(total_bytes);
         }
-        progress_bar.finish();
-        Ok(())
+        destination.flush().map_err(|x| {
+            DownloadError::Failed(save_to_file_error(&selected_asset.name, part_path, x))
+        })?;
+
+        match stream.content_length {
+            Some(length) if total_bytes < length => {
+                Err(DownloadError::Interrupted(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    format!("received {} of {} bytes", total_bytes, length),
+                )))
+            }
+            _ => Ok(hasher.finalize()),
+        }
     }
 
-    fn maybe_install(&self, asset_name: &str, path: &Path) -> Result<(), HandlerError> {
+    fn maybe_install(
+        &self,
+        tag: &Tag,
+        asset: &Asset,
+        path: &Path,
+        sha256: String,
+    ) -> Result<(), HandlerError> {
         match &self.install {
             Install::No => Ok(()),
             Install::Yes(executables) => {
                 let cwd = cwd()?;
-                let destination = match self.output.as_ref() {
-                    Some(output) if output.is_dir() => Destination::Directory(output.clone()),
-                    Some(output) => Destination::File(output.clone()),
-                    None => Destination::Directory(cwd),
-                };
+                let destination = self.destination(&cwd);
                 self.check_destination_invariants(&destination)?;
 
                 let spinner = Spinner::install_layout();
                 spinner.show();
 
                 let output = install(
-                    asset_name.to_string(),
+                    asset.name.to_string(),
                     path,
                     destination,
                     executables.clone(),
-                )
-                .map_err(|x| HandlerError::new(x.to_string()))?;
-
+                );
                 remove_temporary_file(path)?;
+                let output = output.map_err(|x| install_error(asset, x))?;
+                output::emit(Event::InstallFinished {
+                    asset: &asset.name,
+                    files: output.files(),
+                });
+
+                let installation = Installation {
+                    tag,
+                    asset,
+                    files: output.files(),
+                    sha256,
+                    destination: self.output.as_ref().unwrap_or(&cwd),
+                    executables,
+                };
+                self.record_installation(installation);
 
                 let message = format!(
                     "{}\n{}",
@@ -200,21 +591,199 @@ impl DownloadHandler {
         }
     }
 
+    fn destination(&self, cwd: &Path) -> Destination {
+        install_destination(self.output.as_ref(), cwd)
+    }
+
+    fn record_installation(&self, installation: Installation) {
+        let selection = match &self.mode {
+            // NOTE: only one asset can be installed, so there is only one pattern
+            DownloadMode::Selection(patterns) => match &patterns[0] {
+                AssetPattern::Wildcard(x) => InstalledSelection::Pattern(x.clone()),
+                AssetPattern::Regex(x) => InstalledSelection::Regex(x.clone()),
+            },
+            DownloadMode::Automatic => InstalledSelection::Automatic,
+            // NOTE: the same asset will be selected on update
+            DownloadMode::Interactive => InstalledSelection::Pattern(TaggedAsset::untag(
+                installation.tag,
+                installation.asset,
+            )),
+        };
+        let install_files = installation
+            .executables
+            .iter()
+            .filter_map(|x| match x {
+                Executable::Selected(name) => Some(name.clone()),
+                Executable::Automatic(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let tool = InstalledTool {
+            repository: self.repository.to_string(),
+            tag: installation.tag.0.clone(),
+            asset: installation.asset.name.clone(),
+            files: installation.files.iter().map(|x| absolute(x)).collect(),
+            sha256: installation.sha256,
+            installed_at: state::now(),
+            selection,
+            destination: absolute(installation.destination),
+            install_files: if install_files.is_empty() {
+                None
+            } else {
+                Some(install_files)
+            },
+            exclude: if self.exclude.is_empty() {
+                None
+            } else {
+                Some(self.exclude.clone())
+            },
+            tag_requirement: self
+                .tag
+                .as_ref()
+                .filter(|x| matches!(TagRequirement::try_parse(x), Ok(Some(_))))
+                .map(|x| x.0.clone()),
+            include_prereleases: self.latest == LatestRelease::IncludePrereleases,
+            channel: match self.latest {
+                LatestRelease::Channel(channel) => Some(channel),
+                _ => None,
+            },
+            verification: match self.verification {
+                Verification::Disabled => InstalledVerification::Disabled,
+                Verification::ChecksumAsset => InstalledVerification::Release,
+                Verification::Checksum(_) => InstalledVerification::Pinned,
+            },
+            signature: self.signature_key.as_ref().map(|key| {
+                let path = absolute(key.path());
+                if path.to_str().is_some() {
+                    InstalledSignature::Key(path)
+                } else {
+                    InstalledSignature::Unrecorded
+                }
+            }),
+        };
+
+        // NOTE: tools can be installed concurrently (e.g. sync or other dra processes), so only one of them updates the state at a time
+        let result = state::state_file().and_then(|path| {
+            let _lock = InstallState::lock(&path)?;
+            let mut install_state = InstallState::load(&path)?;
+            install_state.record(tool);
+            install_state.save(&path)
+        });
+        if let Err(e) = result {
+            progress::eprintln(&format!(
+                "{}",
+                Color::new(&format!("Warning: unable to record installation: {}", e)).yellow()
+            ));
+        }
+    }
+
     fn check_destination_invariants(&self, destination: &Destination) -> Result<(), HandlerError> {
         if !self.install.is_more_than_one() {
             return Ok(());
         }
-        match destination {
-            Destination::File(x) => {
-                let message = format!(
-                    "{} is not a directory. When you specify multiple executables to install, you must provide a directory path",
-                    x.display()
-                );
-                Err(HandlerError::new(message))
+        check_multiple_executables_destination(destination)
+    }
+}
+
+/// Install into the output directory (or file), default is the current working directory
+pub fn install_destination(output: Option<&PathBuf>, cwd: &Path) -> Destination {
+    match output {
+        Some(output) if output.is_dir() => Destination::Directory(output.clone()),
+        Some(output) => Destination::File(output.clone()),
+        None => Destination::Directory(cwd.to_path_buf()),
+    }
+}
+
+pub fn check_multiple_executables_destination(
+    destination: &Destination,
+) -> Result<(), HandlerError> {
+    match destination {
+        Destination::File(x) => {
+            let message = format!(
+                "{} is not a directory. When you specify multiple executables to install, you must provide a directory path",
+                x.display()
+            );
+            Err(HandlerError::with_kind(ErrorKind::Usage, message))
+        }
+        Destination::Directory(_) => Ok(()),
+    }
+}
+
+struct Installation<'a> {
+    tag: &'a Tag,
+    asset: &'a Asset,
+    files: &'a [PathBuf],
+    sha256: String,
+    destination: &'a Path,
+    executables: &'a [Executable],
+}
+
+fn show_install_plan(asset: &Asset, plan: &InstallPlan) -> HandlerResult {
+    progress::println(&format!("Installer: {}", plan.installer));
+    let (archive_contents, planned, failures): (&[PathBuf], Vec<_>, Vec<_>) = match &plan.target {
+        InstallTarget::PackageManager => {
+            progress::println("The package would be installed by the system package manager");
+            (&[], vec![], vec![])
+        }
+        InstallTarget::Executable(path) => (&[], vec![PlannedExecutable::new(None, path)], vec![]),
+        InstallTarget::Archive(archive) => {
+            progress::println("Archive contents:");
+            for file in &archive.files {
+                progress::println(&format!("  {}", file.display()));
             }
-            Destination::Directory(_) => Ok(()),
+            let (planned, failures) =
+                archive
+                    .executables
+                    .iter()
+                    .partition_map(|executable| match executable {
+                        Ok((from, to)) => Either::Left(PlannedExecutable::new(Some(from), to)),
+                        Err(e) => Either::Right(e),
+                    });
+            (&archive.files, planned, failures)
         }
+    };
+
+    if !planned.is_empty() || !failures.is_empty() {
+        progress::println("Would install:");
     }
+    for executable in &planned {
+        progress::println(&format!("  {} {}", Color::new("✔").green(), executable));
+    }
+    for failure in &failures {
+        progress::println(&format!("  {} {}", Color::new("✘").red(), failure));
+    }
+    output::emit(Event::InstallPlanned {
+        asset: &asset.name,
+        installer: plan.installer,
+        archive_contents,
+        executables: planned,
+        failures: failures
+            .iter()
+            .map(|x| ExecutableFailure::from(*x))
+            .collect(),
+    });
+
+    if !failures.is_empty() {
+        return Err(HandlerError::with_kind(
+            ErrorKind::Install,
+            format!("{} executables would not be installed", failures.len()),
+        ));
+    }
+    Ok(())
+}
+
+pub fn install_error(asset: &Asset, error: InstallError) -> HandlerError {
+    let (installed, failures): (&[String], _) = match &error {
+        InstallError::Archive(x) => (&x.successes, ExecutableFailure::from_archive_error(x)),
+        _ => (&[], vec![]),
+    };
+    output::emit(Event::InstallFailed {
+        asset: &asset.name,
+        message: error.to_string(),
+        installed,
+        failures,
+    });
+    HandlerError::with_kind(ErrorKind::from(&error), error.to_string())
 }
 
 fn ask_select_asset(assets: Vec<Asset>) -> select_assets::AskSelectAssetResult {
@@ -227,15 +796,80 @@ fn ask_select_asset(assets: Vec<Asset>) -> select_assets::AskSelectAssetResult {
     )
 }
 
-fn autoselect_asset(release: Release, selection: &str) -> Result<Asset, HandlerError> {
-    let asset_name = TaggedAsset::tag(&release.tag, selection);
-    let pattern = wildmatch::WildMatch::new(&asset_name);
+fn ask_select_assets(assets: Vec<Asset>) -> select_assets::AskSelectAssetsResult {
+    select_assets::ask_select_assets(
+        assets,
+        select_assets::Messages {
+            select_prompt: "Pick the assets to download (space to select, enter to confirm)",
+            quit_select: "No asset selected",
+        },
+    )
+}
+
+/// One asset for each selection, the same asset is selected only once
+fn autoselect_assets(
+    release: &Release,
+    selections: &[AssetPattern],
+    strict: bool,
+) -> Result<Vec<Asset>, HandlerError> {
+    let mut assets: Vec<Asset> = Vec::new();
+    for selection in selections {
+        let asset = autoselect_asset(release, selection, strict)?;
+        if !assets.iter().any(|x| x.name == asset.name) {
+            assets.push(asset);
+        }
+    }
+    Ok(assets)
+}
 
-    release
-        .assets
-        .into_iter()
-        .find(|x| pattern.matches(&x.name))
-        .ok_or_else(|| HandlerError::new(format!("No asset found for {}", selection)))
+/// First asset matching the selection. When more than one asset matches, all candidates are reported
+/// and in strict mode the selection fails
+pub fn autoselect_asset(
+    release: &Release,
+    selection: &AssetPattern,
+    strict: bool,
+) -> Result<Asset, HandlerError> {
+    let candidates = selection
+        .matching_assets(release)
+        .map_err(|e| HandlerError::with_kind(ErrorKind::Usage, e))?;
+    if candidates.len() > 1 && strict {
+        return Err(HandlerError::with_kind(
+            ErrorKind::NoAssetMatched,
+            format!(
+                "{} matches {} assets:\n{}\nUse a more specific pattern or --exclude to select only one of them",
+                selection,
+                candidates.len(),
+                candidates
+                    .iter()
+                    .map(|x| format!("  {}", x.name))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+        ));
+    }
+    if candidates.len() > 1 {
+        progress::eprintln(&format!(
+            "{}",
+            Color::new(&format!(
+                "Warning: {} matches {} assets ({}), selecting {}",
+                selection,
+                candidates.len(),
+                candidates
+                    .iter()
+                    .map(|x| x.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                candidates[0].name
+            ))
+            .yellow()
+        ));
+    }
+    candidates.first().map(|x| (*x).clone()).ok_or_else(|| {
+        HandlerError::with_kind(
+            ErrorKind::NoAssetMatched,
+            format!("No asset found for {}", selection),
+        )
+    })
 }
 
 fn automatic_download_system_error(
@@ -256,10 +890,13 @@ fn automatic_download_system_error(
         "https://github.com/devmatteini/dra/issues/new?title={}&body={}",
         title, body
     );
-    HandlerError::new(format!(
-        "There was an error determining your system configuration for automatic download: {}\nPlease report the issue: {}\n",
-        error, issue_url
-    ))
+    HandlerError::with_kind(
+        ErrorKind::from(&error),
+        format!(
+            "There was an error determining your system configuration for automatic download: {}\nPlease report the issue: {}\n",
+            error, issue_url
+        ),
+    )
 }
 
 fn automatic_download_error(
@@ -281,25 +918,29 @@ fn automatic_download_error(
         "https://github.com/devmatteini/dra/issues/new?title={}&body={}",
         title, body
     );
-    HandlerError::new(format!(
-        "Cannot find asset that matches your system {} {}\nIf you think this is a bug, please report the issue: {}",
-        system.os(),
-        system.arch(),
-        issue_url
-    ))
+    HandlerError::with_kind(
+        ErrorKind::NoAssetMatched,
+        format!(
+            "Cannot find asset that matches your system {} {}\nIf you think this is a bug, please report the issue: {}",
+            system.os(),
+            system.arch(),
+            issue_url
+        ),
+    )
 }
 
+/// Assets to install are downloaded to `install_path`
 fn choose_output_path_from<IsDir>(
     output: Option<&PathBuf>,
-    install: bool,
+    install_path: Option<PathBuf>,
     asset_name: &str,
     is_dir: IsDir,
 ) -> PathBuf
 where
     IsDir: FnOnce(&Path) -> bool,
 {
-    if install {
-        return crate::temp_file::temp_file();
+    if let Some(path) = install_path {
+        return path;
     }
 
     output
@@ -314,7 +955,81 @@ where
 }
 
 fn download_asset_error(e: GithubError) -> HandlerError {
-    HandlerError::new(format!("Error downloading asset: {}", e))
+    HandlerError::with_kind(
+        ErrorKind::from(&e),
+        format!("Error downloading asset: {}", e),
+    )
+}
+
+/// Temporary file of an asset to install. It's the same for the same asset of a release,
+/// so an interrupted download is resumed by running the same command again
+pub fn install_temp_file(repository: &Repository, tag: &Tag, asset_name: &str) -> PathBuf {
+    crate::temp_file::temp_file_for(&format!("{}/{}/{}", repository, tag.0, asset_name))
+}
+
+/// The asset is downloaded next to the output path and renamed once completed,
+/// so an interrupted download can be resumed by running the same command again
+fn part_file_path(output_path: &Path) -> PathBuf {
+    let mut part = output_path.as_os_str().to_os_string();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+/// Where the bytes of the `.part` file come from
+#[derive(Serialize, Deserialize)]
+struct ResumeInfo {
+    url: String,
+    /// ETag or Last-Modified of the asset when the download started
+    validator: String,
+}
+
+fn resume_info_path(part_path: &Path) -> PathBuf {
+    let mut path = part_path.as_os_str().to_os_string();
+    path.push(".json");
+    PathBuf::from(path)
+}
+
+fn read_resume_info(part_path: &Path) -> Option<ResumeInfo> {
+    let content = std::fs::read_to_string(resume_info_path(part_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Without a validator the download can't be resumed safely, so it will start over
+fn write_resume_info(part_path: &Path, asset: &Asset, validator: Option<String>) {
+    let info = validator.map(|validator| ResumeInfo {
+        url: asset.download_url.clone(),
+        validator,
+    });
+    match info.and_then(|x| serde_json::to_string(&x).ok()) {
+        Some(content) => {
+            let _ = std::fs::write(resume_info_path(part_path), content);
+        }
+        None => remove_resume_info(part_path),
+    }
+}
+
+fn remove_resume_info(part_path: &Path) {
+    let _ = std::fs::remove_file(resume_info_path(part_path));
+}
+
+/// Open the partially downloaded file to append the remaining bytes, hashing the ones already downloaded
+fn open_part_file(path: &Path, hasher: &mut checksum::Hasher) -> Result<File, HandlerError> {
+    let error = |e: std::io::Error| {
+        HandlerError::new(format!("Failed to open file {}: {}", path.display(), e))
+    };
+    let mut file = File::open(path).map_err(error)?;
+    std::io::copy(&mut file, hasher).map_err(error)?;
+    OpenOptions::new().append(true).open(path).map_err(error)
+}
+
+fn download_interrupted_error(asset_name: &str, error: std::io::Error) -> HandlerError {
+    HandlerError::with_kind(
+        ErrorKind::Network,
+        format!(
+            "Download of {} interrupted: {}\nRun the same command again to resume it",
+            asset_name, error
+        ),
+    )
 }
 
 fn create_file(path: &Path) -> Result<File, HandlerError> {
@@ -331,12 +1046,16 @@ fn save_to_file_error(asset_name: &str, output_path: &Path, error: std::io::Erro
     ))
 }
 
-fn cwd() -> Result<PathBuf, HandlerError> {
+pub fn cwd() -> Result<PathBuf, HandlerError> {
     std::env::current_dir()
         .map_err(|x| HandlerError::new(format!("Error retrieving current directory: {}", x)))
 }
 
-fn remove_temporary_file(path: &Path) -> Result<(), HandlerError> {
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+pub fn remove_temporary_file(path: &Path) -> Result<(), HandlerError> {
     std::fs::remove_file(path)
         .map_err(|x| HandlerError::new(format!("Unable to delete temporary file: {}", x)))
 }
@@ -347,8 +1066,7 @@ mod choose_output_path {
 
     use super::*;
 
-    const INSTALL: bool = true;
-    const NO_INSTALL: bool = false;
+    const NO_INSTALL: Option<PathBuf> = None;
     const ANY_ASSET_NAME: &str = "ANY_ASSET_NAME";
 
     /// CLI command:
@@ -356,8 +1074,17 @@ mod choose_output_path {
     #[test_case(Some(PathBuf::from("/some/path")); "any_custom_output")]
     #[test_case(None; "no_output")]
     fn install_mode(output: Option<PathBuf>) {
-        let result = choose_output_path_from(output.as_ref(), INSTALL, ANY_ASSET_NAME, not_dir);
+        let install_path =
+            install_temp_file(&any_repository(), &Tag("1.0.0".into()), ANY_ASSET_NAME);
+
+        let result = choose_output_path_from(
+            output.as_ref(),
+            Some(install_path.clone()),
+            ANY_ASSET_NAME,
+            not_dir,
+        );
 
+        assert_eq!(install_path, result);
         assert!(
             result
                 .to_str()
@@ -366,6 +1093,26 @@ mod choose_output_path {
         )
     }
 
+    #[test]
+    fn install_temp_file_is_the_same_for_the_same_asset() {
+        let repository = any_repository();
+
+        let result = install_temp_file(&repository, &Tag("1.0.0".into()), ANY_ASSET_NAME);
+
+        assert_eq!(
+            install_temp_file(&repository, &Tag("1.0.0".into()), ANY_ASSET_NAME),
+            result
+        );
+        assert_ne!(
+            install_temp_file(&repository, &Tag("1.0.1".into()), ANY_ASSET_NAME),
+            result
+        );
+    }
+
+    fn any_repository() -> Repository {
+        Repository::try_parse("foo/bar").unwrap()
+    }
+
     /// CLI command:
     /// dra download -s my_asset.deb <REPO>
     /// output: $PWD/my_asset.deb
@@ -414,8 +1161,12 @@ mod choose_output_path {
 #[cfg(test)]
 mod autoselect_asset {
     use super::*;
+    use crate::github::release::ReleaseDetails;
     use test_case::test_case;
 
+    const STRICT: bool = true;
+    const NOT_STRICT: bool = false;
+
     #[test]
     fn untagged_selection() {
         let release = any_release(
@@ -427,7 +1178,7 @@ mod autoselect_asset {
             ],
         );
 
-        let result = autoselect_asset(release, "my_asset_v{tag}.zip");
+        let result = autoselect_asset(&release, &wildcard("my_asset_v{tag}.zip"), NOT_STRICT);
 
         assert_ok_and_equal(result, "my_asset_v1.0.0.zip");
     }
@@ -443,7 +1194,7 @@ mod autoselect_asset {
             ],
         );
 
-        let result = autoselect_asset(release, "my_asset.tar.gz");
+        let result = autoselect_asset(&release, &wildcard("my_asset.tar.gz"), NOT_STRICT);
 
         assert_ok_and_equal(result, "my_asset.tar.gz");
     }
@@ -459,7 +1210,7 @@ mod autoselect_asset {
             ],
         );
 
-        let result = autoselect_asset(release, "my_asset_*.zip");
+        let result = autoselect_asset(&release, &wildcard("my_asset_*.zip"), NOT_STRICT);
 
         assert_ok_and_equal(result, "my_asset_abcd.zip");
     }
@@ -475,7 +1226,7 @@ mod autoselect_asset {
             ],
         );
 
-        let result = autoselect_asset(release, "my_asset-v{tag}_*.zip");
+        let result = autoselect_asset(&release, &wildcard("my_asset-v{tag}_*.zip"), NOT_STRICT);
 
         assert_ok_and_equal(result, "my_asset-v1.0.0_abcd.zip");
     }
@@ -494,13 +1245,99 @@ mod autoselect_asset {
             ],
         );
 
-        let result = autoselect_asset(release, selection);
+        let result = autoselect_asset(&release, &wildcard(selection), NOT_STRICT);
 
         assert_err(result);
     }
 
+    #[test]
+    fn multiple_selections() {
+        let release = any_release(
+            "v1.0.0",
+            vec!["my_asset_v1.0.0.tar.gz", "completions.zip", "my_asset.1"],
+        );
+        let selections = ["my_asset_v{tag}.tar.gz", "completions.zip", "*.tar.gz"].map(wildcard);
+
+        let result = autoselect_assets(&release, &selections, NOT_STRICT);
+
+        let names = result
+            .unwrap()
+            .into_iter()
+            .map(|x| x.name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["my_asset_v1.0.0.tar.gz", "completions.zip"], names);
+    }
+
+    #[test]
+    fn multiple_selections_one_missing() {
+        let release = any_release("v1.0.0", vec!["my_asset_v1.0.0.tar.gz"]);
+        let selections = ["my_asset_v{tag}.tar.gz", "completions.zip"].map(wildcard);
+
+        let result = autoselect_assets(&release, &selections, NOT_STRICT);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn ambiguous_selection_strict() {
+        let release = any_release(
+            "v1.0.0",
+            vec!["my_asset-linux-gnu.tar.gz", "my_asset-linux-musl.tar.gz"],
+        );
+
+        let result = autoselect_asset(&release, &wildcard("my_asset-linux-*.tar.gz"), STRICT);
+
+        assert_err(result);
+    }
+
+    #[test]
+    fn regex_selection() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset-v1.0.0-x86_64-linux-musl.tar.gz",
+                "my_asset-v1.0.0-x86_64-linux-gnu.tar.gz",
+            ],
+        );
+
+        let result = autoselect_asset(
+            &release,
+            &AssetPattern::Regex("v{tag}-x86_64-linux-gnu".into()),
+            NOT_STRICT,
+        );
+
+        assert_ok_and_equal(result, "my_asset-v1.0.0-x86_64-linux-gnu.tar.gz");
+    }
+
+    #[test]
+    fn excluded_assets() {
+        let release = any_release(
+            "v1.0.0",
+            vec![
+                "my_asset-linux-musl.tar.gz",
+                "my_asset-linux-gnu.tar.gz",
+                "my_asset-linux-gnu.tar.gz.sha256",
+            ],
+        );
+        let exclude = ["*musl*", "*.sha256"].map(String::from);
+
+        let result = exclude_assets(release, &exclude);
+
+        let names = result
+            .assets
+            .into_iter()
+            .map(|x| x.name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["my_asset-linux-gnu.tar.gz"], names);
+    }
+
+    fn wildcard(pattern: &str) -> AssetPattern {
+        AssetPattern::Wildcard(pattern.to_string())
+    }
+
     fn any_release(tag: &str, asset_names: Vec<&str>) -> Release {
         Release {
+            details: ReleaseDetails::default(),
             tag: Tag(tag.into()),
             assets: asset_names
                 .into_iter()
@@ -508,6 +1345,7 @@ mod autoselect_asset {
                     name: name.into(),
                     display_name: None,
                     download_url: "any".into(),
+                    digest: None,
                 })
                 .collect(),
         }
@@ -526,3 +1364,32 @@ mod autoselect_asset {
         }
     }
 }
+
+#[cfg(test)]
+mod part_file {
+    use super::*;
+
+    #[test]
+    fn next_to_output_path() {
+        let result = part_file_path(Path::new("/some/path/helloworld.tar.gz"));
+
+        assert_eq!(PathBuf::from("/some/path/helloworld.tar.gz.part"), result);
+    }
+
+    #[test]
+    fn resumed_download_digests() {
+        let path = crate::temp_file::temp_file();
+        std::fs::write(&path, "hello ").unwrap();
+        let mut hasher = checksum::Hasher::new();
+
+        let mut file = open_part_file(&path, &mut hasher).unwrap();
+        file.write_all(b"world").unwrap();
+        hasher.update(b"world");
+
+        let mut expected = checksum::Hasher::new();
+        expected.update(b"hello world");
+        assert_eq!("hello world", std::fs::read_to_string(&path).unwrap());
+        assert_eq!(expected.finalize(), hasher.finalize());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
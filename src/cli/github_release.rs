@@ -1,6 +1,9 @@
 use crate::cli::color::Color;
-use crate::cli::result::HandlerError;
+use crate::cli::output::{self, Event};
+use crate::cli::release_channel::LatestRelease;
+use crate::cli::result::{ErrorKind, HandlerError};
 use crate::cli::spinner::Spinner;
+use crate::cli::tag_requirement::TagRequirement;
 use crate::github::client::GithubClient;
 use crate::github::error::GithubError;
 use crate::github::release::{Release, Tag};
@@ -10,24 +13,103 @@ pub fn fetch_release_for(
     github: &GithubClient,
     repository: &Repository,
     tag: Option<&Tag>,
+    latest: LatestRelease,
 ) -> Result<Release, HandlerError> {
     let spinner = Spinner::empty_layout();
     spinner.show();
 
-    let release = github.get_release(repository, tag).map_err(release_error)?;
+    let release = find_release(github, repository, tag, latest)?;
 
-    let message = format!("Release tag is {}", Color::new(&release.tag.0).bold());
+    let message = match release_flags(&release) {
+        Some(flags) => format!(
+            "Release tag is {} ({})",
+            Color::new(&release.tag.0).bold(),
+            flags
+        ),
+        None => format!("Release tag is {}", Color::new(&release.tag.0).bold()),
+    };
     spinner.finish_with_message(&message);
+    output::emit(Event::ReleaseResolved {
+        repository: repository.to_string(),
+        tag: &release.tag.0,
+        prerelease: release.details.prerelease,
+        draft: release.details.draft,
+    });
     Ok(release)
 }
 
+/// Release with the given tag (or the highest one matching a tag requirement), otherwise the latest release
+pub fn find_release(
+    github: &GithubClient,
+    repository: &Repository,
+    tag: Option<&Tag>,
+    latest: LatestRelease,
+) -> Result<Release, HandlerError> {
+    let requirement = tag
+        .map(TagRequirement::try_parse)
+        .transpose()
+        .map_err(|e| HandlerError::with_kind(ErrorKind::Usage, e))?
+        .flatten();
+    match requirement {
+        Some(requirement) => requirement
+            .select(github.releases(repository))
+            .map_err(release_error)?
+            .ok_or_else(|| {
+                HandlerError::with_kind(
+                    ErrorKind::NotFound,
+                    format!("No release found matching '{}'", requirement),
+                )
+            }),
+        None if tag.is_none() && latest != LatestRelease::Stable => {
+            fetch_latest_release(github, repository, latest)
+        }
+        None => github.get_release(repository, tag).map_err(release_error),
+    }
+}
+
+/// Newest release from the list of releases, since the latest release endpoint excludes prereleases
+fn fetch_latest_release(
+    github: &GithubClient,
+    repository: &Repository,
+    latest: LatestRelease,
+) -> Result<Release, HandlerError> {
+    for release in github.releases(repository) {
+        let release = release.map_err(release_error)?;
+        if latest.matches(&release) {
+            return Ok(release);
+        }
+    }
+    Err(HandlerError::with_kind(
+        ErrorKind::NotFound,
+        "No release found matching the requested channel".into(),
+    ))
+}
+
+fn release_flags(release: &Release) -> Option<String> {
+    let flags = [
+        (release.details.prerelease, "prerelease"),
+        (release.details.draft, "draft"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, name)| *name)
+    .collect::<Vec<_>>();
+    (!flags.is_empty()).then(|| flags.join(", "))
+}
+
 fn release_error(e: GithubError) -> HandlerError {
-    HandlerError::new(format!("Error fetching release: {}", e))
+    HandlerError::with_kind(
+        ErrorKind::from(&e),
+        format!("Error fetching release: {}", e),
+    )
 }
 
 pub fn check_has_assets(release: &Release) -> Result<(), HandlerError> {
     if release.assets.is_empty() {
-        Err(HandlerError::new("No assets found for this release".into()))
+        Err(HandlerError::with_kind(
+            ErrorKind::NoAssetMatched,
+            "No assets found for this release".into(),
+        ))
     } else {
         Ok(())
     }
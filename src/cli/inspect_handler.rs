@@ -0,0 +1,178 @@
+use crate::cli::asset_pattern::AssetPattern;
+use crate::cli::color::Color;
+use crate::cli::download_handler::{DownloadHandler, autoselect_asset, install_temp_file};
+use crate::cli::github_release::{check_has_assets, fetch_release_for};
+use crate::cli::output::{self, Event, InspectedFile};
+use crate::cli::progress;
+use crate::cli::release_channel::LatestRelease;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::cli::select_assets;
+use crate::cli::spinner::Spinner;
+use crate::github::client::{ClientOptions, GithubClient};
+use crate::github::release::{Asset, Release, Tag};
+use crate::github::repository::Repository;
+use crate::installer;
+use crate::installer::{InspectedContents, Inspection};
+use std::path::Path;
+
+/// Download an asset to a temporary file and show what the installer would find in it
+pub struct InspectHandler {
+    repository: Repository,
+    selection: Option<AssetPattern>,
+    tag: Option<Tag>,
+    client_options: ClientOptions,
+}
+
+impl InspectHandler {
+    pub fn new(
+        repository: Repository,
+        selection: Option<AssetPattern>,
+        tag: Option<String>,
+        client_options: ClientOptions,
+    ) -> Self {
+        InspectHandler {
+            repository,
+            selection,
+            tag: tag.map(Tag),
+            client_options,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let github = GithubClient::from_environment(&self.client_options);
+        let release = fetch_release_for(
+            &github,
+            &self.repository,
+            self.tag.as_ref(),
+            LatestRelease::Stable,
+        )?;
+        check_has_assets(&release)?;
+        let tag = release.tag.clone();
+        let asset = self.select_asset(release)?;
+        output::emit(Event::AssetSelected {
+            repository: self.repository.to_string(),
+            asset: &asset.name,
+            download_url: &asset.download_url,
+        });
+
+        let path = install_temp_file(&self.repository, &tag, &asset.name);
+        let inspection = DownloadHandler::download_asset(&github, &asset, &path)
+            .and_then(|_| Self::inspect(&asset, &path));
+        let _ = std::fs::remove_file(&path);
+        let inspection = inspection?;
+
+        if output::is_json() {
+            emit_inspection(&asset, &inspection);
+        } else {
+            print_inspection(&inspection);
+        }
+        Ok(())
+    }
+
+    fn select_asset(&self, release: Release) -> Result<Asset, HandlerError> {
+        match &self.selection {
+            Some(selection) => autoselect_asset(&release, selection, false),
+            None => select_assets::ask_select_asset(
+                release.assets,
+                select_assets::Messages {
+                    select_prompt: "Pick the asset to inspect",
+                    quit_select: "No asset selected",
+                },
+            ),
+        }
+    }
+
+    fn inspect(asset: &Asset, path: &Path) -> Result<Inspection, HandlerError> {
+        let spinner = Spinner::inspect_layout();
+        spinner.show();
+        let inspection = installer::inspect(&asset.name, path).map_err(|e| {
+            HandlerError::with_kind(
+                ErrorKind::from(&e),
+                format!("Error inspecting {}: {}", asset.name, e),
+            )
+        });
+        spinner.finish();
+        inspection
+    }
+}
+
+fn print_inspection(inspection: &Inspection) {
+    progress::println(&format!(
+        "{} {}",
+        Color::new("File type:").bold(),
+        inspection.file_type
+    ));
+    match &inspection.contents {
+        InspectedContents::Archive(contents) => {
+            progress::println(&format!("{}", Color::new("Archive contents:").bold()));
+            for file in &contents.files {
+                progress::println(&format!("  {}", file.display()));
+            }
+            if contents.executables.is_empty() {
+                progress::println(&format!("{}", Color::new("No executables found").yellow()));
+                return;
+            }
+            progress::println(&format!("{}", Color::new("Executables:").bold()));
+            for executable in &contents.executables {
+                progress::println(&format!(
+                    "  {} ({})",
+                    executable.path.display(),
+                    show_format(executable.format.as_deref())
+                ));
+            }
+        }
+        InspectedContents::Executable(format) => {
+            progress::println(&format!(
+                "{} {}",
+                Color::new("Executable:").bold(),
+                show_format(format.as_deref())
+            ));
+        }
+        InspectedContents::Package(Ok(metadata)) => {
+            progress::println(&format!("{}", Color::new("Package metadata:").bold()));
+            for line in metadata.lines() {
+                progress::println(&format!("  {}", line));
+            }
+        }
+        InspectedContents::Package(Err(e)) => {
+            progress::println(&format!(
+                "{}",
+                Color::new(&format!("Package metadata not available: {}", e)).yellow()
+            ));
+        }
+    }
+}
+
+fn emit_inspection(asset: &Asset, inspection: &Inspection) {
+    let (files, executables, package_metadata) = match &inspection.contents {
+        InspectedContents::Archive(contents) => (
+            contents.files.as_slice(),
+            contents
+                .executables
+                .iter()
+                .map(|x| InspectedFile::new(&x.path, x.format.as_deref()))
+                .collect(),
+            None,
+        ),
+        InspectedContents::Executable(format) => (
+            &[][..],
+            vec![InspectedFile::new(
+                Path::new(&asset.name),
+                format.as_deref(),
+            )],
+            None,
+        ),
+        InspectedContents::Package(metadata) => (&[][..], vec![], metadata.as_deref().ok()),
+    };
+    output::emit(Event::Inspected {
+        asset: &asset.name,
+        file_type: &inspection.file_type,
+        files,
+        executables,
+        package_metadata,
+    });
+}
+
+fn show_format(format: Option<&str>) -> &str {
+    format.unwrap_or("unknown format")
+}
@@ -0,0 +1,190 @@
+use crate::cli::color::Color;
+use crate::cli::download_handler::{
+    DownloadHandler, check_multiple_executables_destination, cwd, install_destination,
+    install_error, remove_temporary_file,
+};
+use crate::cli::output::{self, Event};
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::cli::spinner::Spinner;
+use crate::github::client::{ClientOptions, GithubClient};
+use crate::github::release::Asset;
+use crate::installer::executable::Executable;
+use crate::installer::install;
+use crate::{temp_file, vector};
+use std::path::{Path, PathBuf};
+
+/// Install a local file or an asset from any URL, without fetching a release
+pub struct InstallHandler {
+    source: InstallSource,
+    output: Option<PathBuf>,
+    install_file: Option<Vec<String>>,
+    client_options: ClientOptions,
+}
+
+#[derive(Debug, PartialEq)]
+enum InstallSource {
+    File(PathBuf),
+    Url(String),
+}
+
+impl InstallSource {
+    fn new(source: String) -> Self {
+        if source.starts_with("https://") || source.starts_with("http://") {
+            Self::Url(source)
+        } else {
+            Self::File(PathBuf::from(source))
+        }
+    }
+
+    /// File name used to find the installer, from the path or the last segment of the url
+    fn asset_name(&self) -> Option<String> {
+        match self {
+            Self::File(path) => path.file_name().map(|x| x.to_string_lossy().to_string()),
+            Self::Url(url) => url
+                .split(['?', '#'])
+                .next()
+                .and_then(|x| x.rsplit('/').next())
+                .filter(|x| !x.is_empty())
+                .map(String::from),
+        }
+    }
+}
+
+impl InstallHandler {
+    pub fn new(
+        source: String,
+        output: Option<PathBuf>,
+        install_file: Option<Vec<String>>,
+        client_options: ClientOptions,
+    ) -> Self {
+        InstallHandler {
+            source: InstallSource::new(source),
+            output,
+            install_file,
+            client_options,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let asset_name = self.source.asset_name().ok_or_else(|| {
+            HandlerError::with_kind(
+                ErrorKind::Usage,
+                "Unable to find the file name of the asset to install".into(),
+            )
+        })?;
+        let asset = Asset {
+            name: asset_name,
+            display_name: None,
+            download_url: match &self.source {
+                InstallSource::Url(url) => url.clone(),
+                InstallSource::File(path) => path.display().to_string(),
+            },
+            digest: None,
+        };
+
+        match &self.source {
+            InstallSource::File(path) => self.install(&asset, path),
+            InstallSource::Url(url) => {
+                let github = GithubClient::from_environment(&self.client_options);
+                // NOTE: the same url is downloaded to the same file, so an interrupted download is resumed
+                let path = temp_file::temp_file_for(url);
+                DownloadHandler::download_asset(&github, &asset, &path)?;
+                let result = self.install(&asset, &path);
+                remove_temporary_file(&path)?;
+                result
+            }
+        }
+    }
+
+    fn install(&self, asset: &Asset, path: &Path) -> HandlerResult {
+        let executables = self.executables(&asset.name);
+        let cwd = cwd()?;
+        let destination = install_destination(self.output.as_ref(), &cwd);
+        if executables.len() > 1 {
+            check_multiple_executables_destination(&destination)?;
+        }
+
+        let spinner = Spinner::install_layout();
+        spinner.show();
+        let output = install(asset.name.clone(), path, destination, executables)
+            .map_err(|x| install_error(asset, x))?;
+        output::emit(Event::InstallFinished {
+            asset: &asset.name,
+            files: output.files(),
+        });
+        let message = format!(
+            "{}\n{}",
+            output,
+            Color::new("Installation completed!").green(),
+        );
+        spinner.finish_with_message(&message);
+        Ok(())
+    }
+
+    fn executables(&self, asset_name: &str) -> Vec<Executable> {
+        match &self.install_file {
+            Some(names) => vector::unique(names.clone())
+                .into_iter()
+                .map(Executable::Selected)
+                .collect(),
+            None => vec![Executable::Automatic(executable_name(asset_name))],
+        }
+    }
+}
+
+/// Tool name guessed from the asset name, without version, os and architecture
+/// (e.g. ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz -> ripgrep).
+/// Archives with only one executable don't depend on it
+fn executable_name(asset_name: &str) -> String {
+    let stem = asset_name.split(['_', '.']).next().unwrap_or(asset_name);
+    let name = stem
+        .split('-')
+        .take_while(|x| !is_version(x))
+        .collect::<Vec<_>>()
+        .join("-");
+    if name.is_empty() {
+        stem.to_string()
+    } else {
+        name
+    }
+}
+
+fn is_version(segment: &str) -> bool {
+    segment
+        .strip_prefix('v')
+        .unwrap_or(segment)
+        .starts_with(|x: char| x.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("https://example.com/tool.tar.gz", InstallSource::Url("https://example.com/tool.tar.gz".into()); "https url")]
+    #[test_case("http://example.com/tool.tar.gz", InstallSource::Url("http://example.com/tool.tar.gz".into()); "http url")]
+    #[test_case("./tool.tar.gz", InstallSource::File(PathBuf::from("./tool.tar.gz")); "relative path")]
+    #[test_case("/tmp/tool.tar.gz", InstallSource::File(PathBuf::from("/tmp/tool.tar.gz")); "absolute path")]
+    fn install_source(source: &str, expected: InstallSource) {
+        assert_eq!(expected, InstallSource::new(source.to_string()));
+    }
+
+    #[test_case("https://example.com/download/tool.tar.gz", Some("tool.tar.gz"); "url")]
+    #[test_case("https://example.com/tool.deb?token=abc#x", Some("tool.deb"); "url with query")]
+    #[test_case("https://example.com/", None; "url without file name")]
+    #[test_case("/tmp/tool.zip", Some("tool.zip"); "path")]
+    fn asset_name(source: &str, expected: Option<&str>) {
+        let result = InstallSource::new(source.to_string()).asset_name();
+
+        assert_eq!(expected.map(String::from), result);
+    }
+
+    #[test_case("ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz", "ripgrep"; "version")]
+    #[test_case("fd-v10.2.0-x86_64-unknown-linux-gnu.tar.gz", "fd"; "version with v prefix")]
+    #[test_case("helloworld_0.1.2_amd64.deb", "helloworld"; "underscore")]
+    #[test_case("my-tool-linux.tar.gz", "my-tool-linux"; "without version")]
+    #[test_case("1.0.0.tar.gz", "1"; "only version")]
+    fn executable_name_from_asset(asset_name: &str, expected: &str) {
+        assert_eq!(expected, executable_name(asset_name));
+    }
+}
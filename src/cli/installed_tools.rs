@@ -0,0 +1,107 @@
+use crate::cli::color::Color;
+use crate::cli::github_release::find_release;
+use crate::cli::progress;
+use crate::cli::release_channel::LatestRelease;
+use crate::cli::result::HandlerError;
+use crate::github::client::GithubClient;
+use crate::github::release::{Release, Tag};
+use crate::github::repository::Repository;
+use crate::state;
+use crate::state::{InstallState, InstalledTool};
+use semver::Version;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+pub fn load_install_state() -> Result<(PathBuf, InstallState), HandlerError> {
+    let path = state::state_file().map_err(HandlerError::new)?;
+    let install_state = InstallState::load(&path).map_err(HandlerError::new)?;
+    Ok((path, install_state))
+}
+
+pub fn fetch_latest_release(
+    github: &GithubClient,
+    tool: &InstalledTool,
+) -> Result<(Repository, Release), HandlerError> {
+    let repository = Repository::try_parse(&tool.repository).map_err(HandlerError::new)?;
+    let tag_requirement = tool.tag_requirement.as_ref().map(|x| Tag(x.clone()));
+    let latest = LatestRelease::new(tool.include_prereleases, tool.channel);
+    let release = find_release(github, &repository, tag_requirement.as_ref(), latest)?;
+    Ok((repository, release))
+}
+
+/// Order of the `latest` release compared to the `installed` one: semver versions first,
+/// then the numbers of the tags (e.g. `v1.10` or `2024.01.31`).
+/// `None` when the tags can't be compared (e.g. `nightly`), since any of them could be the newer one
+pub fn compare_versions(installed: &str, latest: &str) -> Option<Ordering> {
+    if installed == latest {
+        return Some(Ordering::Equal);
+    }
+
+    let installed = Tag(installed.to_string());
+    let latest = Tag(latest.to_string());
+    if let (Ok(installed), Ok(latest)) = (
+        Version::parse(&installed.without_prefix()),
+        Version::parse(&latest.without_prefix()),
+    ) {
+        return Some(latest.cmp(&installed));
+    }
+
+    let installed = version_numbers(&installed)?;
+    let latest = version_numbers(&latest)?;
+    // NOTE: missing numbers are zeros, so `v1.1` and `v1.1.0` are the same version
+    let length = installed.len().max(latest.len());
+    let pad = |x: Vec<u64>| {
+        x.into_iter()
+            .chain(std::iter::repeat(0))
+            .take(length)
+            .collect::<Vec<_>>()
+    };
+    Some(pad(latest).cmp(&pad(installed)))
+}
+
+/// The tool is left as it is, the user can still install the latest release explicitly
+pub fn warn_not_comparable(repository: &str, installed: &str, latest: &str) {
+    progress::eprintln(&format!(
+        "{}",
+        Color::new(&format!(
+            "{}: unable to tell whether {} is newer than the installed {}, use `dra download --install` to install it",
+            repository, latest, installed
+        ))
+        .yellow()
+    ));
+}
+
+fn version_numbers(tag: &Tag) -> Option<Vec<u64>> {
+    let numbers = tag
+        .version_components()
+        .iter()
+        .map(|x| x.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    (!numbers.is_empty()).then_some(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("v1.0.0", "v1.1.0", Some(Ordering::Greater); "newer version")]
+    #[test_case("v1.1.0", "v1.1.0", Some(Ordering::Equal); "same version")]
+    #[test_case("v2.0.0-rc.1", "v1.9.0", Some(Ordering::Less); "older stable than installed prerelease")]
+    #[test_case("v2.0.0-rc.1", "v2.0.0", Some(Ordering::Greater); "stable of installed prerelease")]
+    #[test_case("v1.10.0", "v1.9.0", Some(Ordering::Less); "older version with more digits")]
+    #[test_case("1.0.0", "v1.0.0", Some(Ordering::Equal); "same version with different prefix")]
+    #[test_case("v1.0", "v1.1", Some(Ordering::Greater); "newer non semver version")]
+    #[test_case("v1.1", "v1.0", Some(Ordering::Less); "older non semver version")]
+    #[test_case("v1.1", "v1.1.0", Some(Ordering::Equal); "same version with less numbers")]
+    #[test_case("2024.01.31", "2024.02.01", Some(Ordering::Greater); "newer date")]
+    #[test_case("2024.02.01", "2024.01.31", Some(Ordering::Less); "older date")]
+    #[test_case("nightly", "nightly", Some(Ordering::Equal); "same tag without version")]
+    #[test_case("nightly", "nightly-2", None; "different tag without version")]
+    #[test_case("v1.0", "nightly", None; "tag without version")]
+    fn compare_installed_and_latest(installed: &str, latest: &str, expected: Option<Ordering>) {
+        let result = compare_versions(installed, latest);
+
+        assert_eq!(expected, result);
+    }
+}
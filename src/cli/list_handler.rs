@@ -0,0 +1,43 @@
+use crate::cli::color::Color;
+use crate::cli::output::{self, Event};
+use crate::cli::result::{HandlerError, HandlerResult};
+use crate::state;
+use crate::state::InstallState;
+
+pub struct ListHandler;
+
+impl ListHandler {
+    pub fn new() -> Self {
+        ListHandler
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let path = state::state_file().map_err(HandlerError::new)?;
+        let install_state = InstallState::load(&path).map_err(HandlerError::new)?;
+
+        if output::is_json() {
+            output::emit(Event::InstalledTools {
+                tools: install_state.tools(),
+            });
+            return Ok(());
+        }
+        if install_state.tools().is_empty() {
+            println!("No tools installed with dra");
+            return Ok(());
+        }
+
+        for tool in install_state.tools() {
+            println!(
+                "{} {} ({}) installed at {}",
+                Color::new(&tool.repository).bold(),
+                tool.tag,
+                tool.asset,
+                tool.installed_at
+            );
+            for file in &tool.files {
+                println!("  {}", file.display());
+            }
+        }
+        Ok(())
+    }
+}
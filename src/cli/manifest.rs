@@ -0,0 +1,274 @@
+use crate::checksum::Checksum;
+use crate::github::repository::Repository;
+use crate::signature::PublicKey;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// List of tools to download (and install) with `sync` command
+#[derive(Debug)]
+pub struct Manifest {
+    pub tools: Vec<ManifestTool>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ManifestTool {
+    pub repository: Repository,
+    pub select: Option<String>,
+    pub tag: Option<String>,
+    pub output: Option<PathBuf>,
+    pub install: bool,
+    pub install_file: Option<Vec<String>>,
+    pub verify: bool,
+    pub checksum: Option<Checksum>,
+    pub verify_signature: Option<PublicKey>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ManifestFile {
+    #[serde(default)]
+    tool: Vec<ToolEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ToolEntry {
+    repo: String,
+    select: Option<String>,
+    tag: Option<String>,
+    output: Option<PathBuf>,
+    #[serde(default)]
+    install: bool,
+    install_file: Option<Vec<String>>,
+    #[serde(default)]
+    verify: bool,
+    checksum: Option<String>,
+    verify_signature: Option<PathBuf>,
+}
+
+impl Manifest {
+    pub fn from_file(path: &Path) -> Result<Manifest, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Error reading manifest {}: {}", path.display(), e))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&content, base_dir)
+            .map_err(|e| format!("Invalid manifest {}: {}", path.display(), e))
+    }
+
+    /// Relative paths of public keys are resolved from `base_dir`, the directory of the manifest
+    fn parse(content: &str, base_dir: &Path) -> Result<Manifest, String> {
+        let file: ManifestFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        if file.tool.is_empty() {
+            return Err("No tools found, add at least one [[tool]] entry".to_string());
+        }
+
+        let tools = file
+            .tool
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut entry)| {
+                entry.verify_signature = entry.verify_signature.map(|x| base_dir.join(x));
+                ManifestTool::try_from(entry).map_err(|e| format!("tool #{}: {}", index + 1, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Manifest { tools })
+    }
+}
+
+impl TryFrom<ToolEntry> for ManifestTool {
+    type Error = String;
+
+    fn try_from(entry: ToolEntry) -> Result<Self, Self::Error> {
+        let repository = Repository::try_parse(&entry.repo)?;
+        let checksum = entry
+            .checksum
+            .as_deref()
+            .map(Checksum::try_parse)
+            .transpose()?;
+        let verify_signature = entry
+            .verify_signature
+            .map(|x| PublicKey::try_from_file(&x.to_string_lossy()))
+            .transpose()?;
+        Ok(ManifestTool {
+            repository,
+            select: entry.select,
+            tag: entry.tag,
+            output: entry.output,
+            install: entry.install,
+            install_file: entry.install_file,
+            verify: entry.verify,
+            checksum,
+            verify_signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::tests::fixture;
+
+    #[test]
+    fn all_fields() {
+        let content = r#"
+[[tool]]
+repo = "devmatteini/dra-tests"
+select = "helloworld_{tag}.tar.gz"
+tag = "0.1.0"
+output = "/usr/local/bin"
+install = true
+install_file = ["helloworld"]
+"#;
+
+        let result = Manifest::parse(content, Path::new("")).unwrap();
+
+        assert_eq!(
+            vec![ManifestTool {
+                repository: Repository::try_parse("devmatteini/dra-tests").unwrap(),
+                select: Some("helloworld_{tag}.tar.gz".to_string()),
+                tag: Some("0.1.0".to_string()),
+                output: Some(PathBuf::from("/usr/local/bin")),
+                install: true,
+                install_file: Some(vec!["helloworld".to_string()]),
+                verify: false,
+                checksum: None,
+                verify_signature: None,
+            }],
+            result.tools
+        );
+    }
+
+    #[test]
+    fn verification() {
+        let key = fixture("minisign.pub");
+        let content = format!(
+            r#"
+[[tool]]
+repo = "devmatteini/dra-tests"
+verify = true
+checksum = "sha256:{checksum}"
+verify_signature = "{key}"
+"#,
+            checksum = "a".repeat(64),
+            key = key.display()
+        );
+
+        let result = Manifest::parse(&content, Path::new("")).unwrap();
+
+        let tool = &result.tools[0];
+        assert!(tool.verify);
+        assert_eq!(
+            Some(Checksum::try_parse(&"a".repeat(64)).unwrap()),
+            tool.checksum
+        );
+        assert_eq!(Some(PublicKey::Minisign(key)), tool.verify_signature);
+    }
+
+    #[test]
+    fn public_key_relative_to_manifest() {
+        let content = r#"
+[[tool]]
+repo = "devmatteini/dra-tests"
+verify_signature = "minisign.pub"
+"#;
+
+        let result = Manifest::parse(content, &fixture("")).unwrap();
+
+        assert_eq!(
+            Some(PublicKey::Minisign(fixture("minisign.pub"))),
+            result.tools[0].verify_signature
+        );
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        let content = r#"
+[[tool]]
+repo = "devmatteini/dra"
+checksum = "md5:foo"
+"#;
+
+        let result = Manifest::parse(content, Path::new(""));
+
+        assert_error_contains("tool #1: Unsupported checksum algorithm 'md5'", result);
+    }
+
+    #[test]
+    fn missing_public_key() {
+        let content = r#"
+[[tool]]
+repo = "devmatteini/dra"
+verify_signature = "/missing/minisign.pub"
+"#;
+
+        let result = Manifest::parse(content, Path::new(""));
+
+        assert_error_contains(
+            "tool #1: Error reading public key /missing/minisign.pub",
+            result,
+        );
+    }
+
+    #[test]
+    fn only_repository() {
+        let content = r#"
+[[tool]]
+repo = "https://github.com/devmatteini/dra"
+
+[[tool]]
+repo = "devmatteini/dra-tests"
+"#;
+
+        let result = Manifest::parse(content, Path::new("")).unwrap();
+
+        assert_eq!(2, result.tools.len());
+        let tool = &result.tools[0];
+        assert_eq!("devmatteini/dra", tool.repository.to_string());
+        assert_eq!(None, tool.select);
+        assert!(!tool.install);
+        assert!(!tool.verify);
+    }
+
+    #[test]
+    fn invalid_repository() {
+        let content = r#"
+[[tool]]
+repo = "devmatteini/dra"
+
+[[tool]]
+repo = "dra"
+"#;
+
+        let result = Manifest::parse(content, Path::new(""));
+
+        assert_error_contains("tool #2: Invalid repository", result);
+    }
+
+    #[test]
+    fn unknown_field() {
+        let content = r#"
+[[tool]]
+repo = "devmatteini/dra"
+instal = true
+"#;
+
+        let result = Manifest::parse(content, Path::new(""));
+
+        assert_error_contains("unknown field `instal`", result);
+    }
+
+    #[test]
+    fn no_tools() {
+        let result = Manifest::parse("", Path::new(""));
+
+        assert_error_contains("No tools found", result);
+    }
+
+    fn assert_error_contains(expected: &str, actual: Result<Manifest, String>) {
+        match actual {
+            Ok(manifest) => panic!("Expected Err, got Ok: {:?}", manifest),
+            Err(e) => assert!(e.contains(expected), "'{}' not contains '{}'", e, expected),
+        }
+    }
+}
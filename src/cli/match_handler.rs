@@ -0,0 +1,137 @@
+use crate::cli::asset_pattern::{AssetPattern, exclude_assets};
+use crate::cli::color::Color;
+use crate::cli::github_release::fetch_release_for;
+use crate::cli::output::{self, Event};
+use crate::cli::release_channel::LatestRelease;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::github::client::{ClientOptions, GithubClient};
+use crate::github::release::{Release, Tag};
+use crate::github::repository::Repository;
+
+/// Show which assets every pattern selects, without downloading anything
+pub struct MatchHandler {
+    repository: Repository,
+    patterns: Vec<AssetPattern>,
+    exclude: Vec<String>,
+    tag: Option<Tag>,
+    strict: bool,
+    client_options: ClientOptions,
+}
+
+impl MatchHandler {
+    pub fn new(
+        repository: Repository,
+        patterns: Vec<AssetPattern>,
+        exclude: Vec<String>,
+        tag: Option<String>,
+        strict: bool,
+        client_options: ClientOptions,
+    ) -> Self {
+        MatchHandler {
+            repository,
+            patterns,
+            exclude,
+            tag: tag.map(Tag),
+            strict,
+            client_options,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let github = GithubClient::from_environment(&self.client_options);
+        let release = fetch_release_for(
+            &github,
+            &self.repository,
+            self.tag.as_ref(),
+            LatestRelease::Stable,
+        )?;
+        let release = exclude_assets(release, &self.exclude);
+
+        let mut failures = 0;
+        for pattern in &self.patterns {
+            if !self.print_matches(&release, pattern)? {
+                failures += 1;
+            }
+        }
+
+        if failures > 0 {
+            return Err(HandlerError::with_kind(
+                ErrorKind::NoAssetMatched,
+                format!(
+                    "{} of {} patterns don't select an asset",
+                    failures,
+                    self.patterns.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the pattern selects an asset
+    fn print_matches(
+        &self,
+        release: &Release,
+        pattern: &AssetPattern,
+    ) -> Result<bool, HandlerError> {
+        let candidates = pattern
+            .matching_assets(release)
+            .map_err(|e| HandlerError::with_kind(ErrorKind::Usage, e))?;
+        let is_ambiguous = candidates.len() > 1;
+        if output::is_json() {
+            let selected = candidates
+                .first()
+                .filter(|_| !(is_ambiguous && self.strict))
+                .map(|x| x.name.as_str());
+            output::emit(Event::PatternMatched {
+                pattern: pattern.to_string(),
+                kind: pattern.kind(),
+                tagged_pattern: pattern.tagged(&release.tag),
+                candidates: candidates.iter().map(|x| x.name.as_str()).collect(),
+                selected,
+            });
+            return Ok(selected.is_some());
+        }
+
+        println!(
+            "\n{} ({}: {})",
+            Color::new(&pattern.to_string()).bold(),
+            pattern.kind(),
+            pattern.tagged(&release.tag)
+        );
+
+        let selected = match candidates.split_first() {
+            None => {
+                println!("  {}", Color::new("No asset matches").red());
+                false
+            }
+            Some(_) if is_ambiguous && self.strict => {
+                for asset in &candidates {
+                    println!("  {} {}", Color::new("✘").red(), asset.name);
+                }
+                println!(
+                    "  {}",
+                    Color::new("More than one asset matches (strict mode)").red()
+                );
+                false
+            }
+            Some((first, others)) => {
+                println!("  {} {}", Color::new("✔").green(), first.name);
+                for asset in others {
+                    println!("    {}", asset.name);
+                }
+                if is_ambiguous {
+                    println!(
+                        "  {}",
+                        Color::new(&format!(
+                            "{} assets match, the first one is selected",
+                            candidates.len()
+                        ))
+                        .yellow()
+                    );
+                }
+                true
+            }
+        };
+        Ok(selected)
+    }
+}
@@ -1,10 +1,30 @@
+pub mod asset_pattern;
+mod checksum_verification;
 pub mod color;
 pub mod completion_handler;
 pub mod download_handler;
 mod github_release;
+pub mod inspect_handler;
+pub mod install_handler;
+mod installed_tools;
+pub mod list_handler;
+mod manifest;
+pub mod match_handler;
+pub mod outdated_handler;
+pub mod output;
+pub mod progress;
 mod progress_bar;
+pub mod release_channel;
+pub mod release_filter;
+pub mod releases_handler;
 pub mod result;
 pub mod root_command;
 mod select_assets;
+mod signature_verification;
 mod spinner;
+pub mod sync_handler;
+mod tag_requirement;
+pub mod uninstall_handler;
 pub mod untag_handler;
+pub mod update_handler;
+mod worker_pool;
@@ -0,0 +1,121 @@
+use crate::cli::color::Color;
+use crate::cli::installed_tools::{
+    compare_versions, fetch_latest_release, load_install_state, warn_not_comparable,
+};
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::cli::spinner::Spinner;
+use crate::github::client::{ClientOptions, GithubClient};
+use std::cmp::Ordering;
+
+pub struct OutdatedHandler {
+    client_options: ClientOptions,
+}
+
+struct OutdatedTool {
+    repository: String,
+    installed: String,
+    latest: String,
+}
+
+impl OutdatedHandler {
+    pub fn new(client_options: ClientOptions) -> Self {
+        OutdatedHandler { client_options }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let (_, install_state) = load_install_state()?;
+        if install_state.tools().is_empty() {
+            progress::println("No tools installed with dra");
+            return Ok(());
+        }
+
+        let github = GithubClient::from_environment(&self.client_options);
+        let spinner = Spinner::new("Checking latest releases".into(), String::new());
+        spinner.show();
+        let results: Vec<_> = install_state
+            .tools()
+            .iter()
+            .map(|tool| {
+                fetch_latest_release(&github, tool)
+                    .map(|(_, release)| OutdatedTool {
+                        repository: tool.repository.clone(),
+                        installed: tool.tag.clone(),
+                        latest: release.tag.0,
+                    })
+                    .map_err(|e| (tool.repository.clone(), e))
+            })
+            .collect();
+        spinner.finish();
+
+        let mut outdated = Vec::new();
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(x) => match compare_versions(&x.installed, &x.latest) {
+                    Some(Ordering::Greater) => outdated.push(x),
+                    Some(_) => {}
+                    None => warn_not_comparable(&x.repository, &x.installed, &x.latest),
+                },
+                Err((repository, e)) => {
+                    failures.push(e.kind());
+                    progress::eprintln(&format!(
+                        "{}",
+                        Color::new(&format!("{}: {}", repository, e)).red()
+                    ));
+                }
+            }
+        }
+
+        for x in &outdated {
+            output::emit(Event::ToolOutdated {
+                repository: &x.repository,
+                installed_tag: &x.installed,
+                latest_tag: &x.latest,
+            });
+        }
+        if outdated.is_empty() && failures.is_empty() {
+            progress::println("All tools are up to date");
+        }
+        if !outdated.is_empty() && !output::is_json() {
+            Self::print_table(&outdated);
+        }
+
+        if !failures.is_empty() {
+            return Err(HandlerError::with_kind(
+                ErrorKind::common(failures.iter().copied()),
+                format!(
+                    "Unable to check {} of {} tools",
+                    failures.len(),
+                    install_state.tools().len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn print_table(outdated: &[OutdatedTool]) {
+        let repository_width = column_width("REPOSITORY", outdated.iter().map(|x| &x.repository));
+        let installed_width = column_width("INSTALLED", outdated.iter().map(|x| &x.installed));
+
+        println!(
+            "{}",
+            Color::new(&format!(
+                "{:repository_width$}  {:installed_width$}  LATEST",
+                "REPOSITORY", "INSTALLED"
+            ))
+            .bold()
+        );
+        for x in outdated {
+            println!(
+                "{:repository_width$}  {:installed_width$}  {}",
+                x.repository, x.installed, x.latest
+            );
+        }
+    }
+}
+
+fn column_width<'a>(header: &str, values: impl Iterator<Item = &'a String>) -> usize {
+    values.map(|x| x.len()).max().unwrap_or(0).max(header.len())
+}
@@ -0,0 +1,271 @@
+use crate::cli::releases_handler::ReleaseOutput;
+use crate::installer::error::{ArchiveError, ArchiveErrorType, ArchiveInstallerError};
+use crate::state::InstalledTool;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable output with spinners and colors
+    #[default]
+    Text,
+    /// One JSON event per line on stdout, interactive prompts are disabled
+    Json,
+}
+
+/// Set the output format of the whole process, it can only be set once
+pub fn init(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+pub fn is_json() -> bool {
+    FORMAT.get() == Some(&OutputFormat::Json)
+}
+
+/// Print the event as a JSON line on stdout when the output format is json
+pub fn emit(event: Event) {
+    if !is_json() {
+        return;
+    }
+    // NOTE: events only contain strings, numbers and booleans, so serialization can't fail
+    let line = serde_json::to_string(&event).expect("Unable to serialize output event");
+    println!("{}", line);
+}
+
+/// Structured events printed with `--output-format json`.
+///
+/// Field names are part of the public interface: fields can be added, but never renamed or removed.
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    ReleaseResolved {
+        repository: String,
+        tag: &'a str,
+        prerelease: bool,
+        draft: bool,
+    },
+    AssetSelected {
+        repository: String,
+        asset: &'a str,
+        download_url: &'a str,
+    },
+    DownloadPlanned {
+        asset: &'a str,
+        path: &'a Path,
+    },
+    DownloadFinished {
+        asset: &'a str,
+        path: &'a Path,
+        size: u64,
+        sha256: &'a str,
+    },
+    DownloadFailed {
+        asset: &'a str,
+        message: String,
+    },
+    /// The GitHub API rate limit is exceeded and requests continue after waiting
+    RateLimited {
+        wait_seconds: u64,
+    },
+    ChecksumVerified {
+        asset: &'a str,
+        algorithm: &'a str,
+    },
+    SignatureVerified {
+        asset: &'a str,
+        kind: &'a str,
+    },
+    InstallFinished {
+        asset: &'a str,
+        files: &'a [PathBuf],
+    },
+    InstallPlanned {
+        asset: &'a str,
+        installer: &'a str,
+        archive_contents: &'a [PathBuf],
+        executables: Vec<PlannedExecutable<'a>>,
+        failures: Vec<ExecutableFailure<'a>>,
+    },
+    InstallFailed {
+        asset: &'a str,
+        message: String,
+        installed: &'a [String],
+        failures: Vec<ExecutableFailure<'a>>,
+    },
+    ToolSynced {
+        repository: String,
+        success: bool,
+        message: Option<String>,
+    },
+    ToolUpToDate {
+        repository: &'a str,
+        tag: &'a str,
+    },
+    ToolUpdating {
+        repository: &'a str,
+        installed_tag: &'a str,
+        latest_tag: &'a str,
+    },
+    ToolOutdated {
+        repository: &'a str,
+        installed_tag: &'a str,
+        latest_tag: &'a str,
+    },
+    ToolUninstalled {
+        repository: &'a str,
+        files: &'a [PathBuf],
+    },
+    InstalledTools {
+        tools: &'a [InstalledTool],
+    },
+    Releases {
+        releases: &'a [ReleaseOutput],
+    },
+    PatternMatched {
+        pattern: String,
+        kind: &'static str,
+        tagged_pattern: String,
+        candidates: Vec<&'a str>,
+        selected: Option<&'a str>,
+    },
+    Inspected {
+        asset: &'a str,
+        file_type: &'a str,
+        /// Files of the archive, empty for other file types
+        files: &'a [PathBuf],
+        executables: Vec<InspectedFile<'a>>,
+        package_metadata: Option<&'a str>,
+    },
+    Untagged {
+        asset: &'a str,
+        pattern: &'a str,
+    },
+    Error {
+        kind: &'a str,
+        message: String,
+        exit_code: i32,
+    },
+}
+
+/// An executable that would be installed with `--dry-run`
+#[derive(Serialize, Debug)]
+pub struct PlannedExecutable<'a> {
+    /// Path in the archive
+    source: Option<&'a Path>,
+    destination: &'a Path,
+}
+
+impl<'a> PlannedExecutable<'a> {
+    pub fn new(source: Option<&'a Path>, destination: &'a Path) -> Self {
+        PlannedExecutable {
+            source,
+            destination,
+        }
+    }
+}
+
+impl Display for PlannedExecutable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{} -> {}", source.display(), self.destination.display()),
+            None => write!(f, "{}", self.destination.display()),
+        }
+    }
+}
+
+/// An executable found by `dra inspect`
+#[derive(Serialize, Debug)]
+pub struct InspectedFile<'a> {
+    path: &'a Path,
+    /// Executable format and architecture, none for scripts
+    format: Option<&'a str>,
+}
+
+impl<'a> InspectedFile<'a> {
+    pub fn new(path: &'a Path, format: Option<&'a str>) -> Self {
+        InspectedFile { path, format }
+    }
+}
+
+/// An executable of an archive that could not be installed
+#[derive(Serialize, Debug)]
+pub struct ExecutableFailure<'a> {
+    executable: &'a str,
+    reason: &'static str,
+    message: String,
+    candidates: &'a [String],
+}
+
+impl<'a> ExecutableFailure<'a> {
+    pub fn from_archive_error(error: &'a ArchiveInstallerError) -> Vec<Self> {
+        error.failures.iter().map(Self::from).collect()
+    }
+}
+
+impl<'a> From<&'a ArchiveError> for ExecutableFailure<'a> {
+    fn from(error: &'a ArchiveError) -> Self {
+        let ArchiveError(executable, error_type) = error;
+        let (reason, candidates): (_, &[String]) = match error_type {
+            ArchiveErrorType::ExecutableNotFound => ("not_found", &[]),
+            ArchiveErrorType::TooManyExecutableCandidates(x) => ("too_many_candidates", x),
+            ArchiveErrorType::CopyExecutable(_, _, _) => ("copy_failed", &[]),
+        };
+        ExecutableFailure {
+            executable,
+            reason,
+            message: error.to_string(),
+            candidates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_name_is_snake_case() {
+        let event = Event::ReleaseResolved {
+            repository: "devmatteini/dra".to_string(),
+            tag: "1.0.0",
+            prerelease: false,
+            draft: false,
+        };
+
+        let result = serde_json::to_string(&event).unwrap();
+
+        assert_eq!(
+            r#"{"event":"release_resolved","repository":"devmatteini/dra","tag":"1.0.0","prerelease":false,"draft":false}"#,
+            result
+        );
+    }
+
+    #[test]
+    fn executable_failures() {
+        let error = ArchiveInstallerError {
+            successes: vec![],
+            failures: vec![
+                ArchiveError("mytool".to_string(), ArchiveErrorType::ExecutableNotFound),
+                ArchiveError(
+                    "mytool2".to_string(),
+                    ArchiveErrorType::TooManyExecutableCandidates(vec![
+                        "a/mytool2".to_string(),
+                        "b/mytool2".to_string(),
+                    ]),
+                ),
+            ],
+        };
+
+        let result = serde_json::to_value(ExecutableFailure::from_archive_error(&error)).unwrap();
+
+        assert_eq!("not_found", result[0]["reason"]);
+        assert_eq!("mytool", result[0]["executable"]);
+        assert_eq!("too_many_candidates", result[1]["reason"]);
+        assert_eq!(2, result[1]["candidates"].as_array().unwrap().len());
+    }
+}
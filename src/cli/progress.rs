@@ -0,0 +1,37 @@
+use crate::cli::output;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget};
+use std::sync::LazyLock;
+
+// NOTE: every spinner and progress bar is drawn by the same MultiProgress,
+// so concurrent downloads don't overwrite each other lines.
+// With json output nothing is drawn, so stdout only contains events
+static PROGRESS: LazyLock<MultiProgress> = LazyLock::new(|| {
+    if output::is_json() {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    } else {
+        MultiProgress::new()
+    }
+});
+
+pub fn add(pb: ProgressBar) -> ProgressBar {
+    PROGRESS.add(pb)
+}
+
+pub fn remove(pb: &ProgressBar) {
+    pb.finish_and_clear();
+    PROGRESS.remove(pb);
+}
+
+/// Print to stdout without breaking the progress bars being drawn.
+/// Human readable messages are not printed with json output
+pub fn println(message: &str) {
+    if output::is_json() {
+        return;
+    }
+    PROGRESS.suspend(|| println!("{}", message));
+}
+
+/// Print to stderr without breaking the progress bars being drawn
+pub fn eprintln(message: &str) {
+    PROGRESS.suspend(|| eprintln!("{}", message));
+}
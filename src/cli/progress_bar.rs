@@ -2,6 +2,7 @@ use indicatif::ProgressStyle;
 use std::path::Path;
 
 use crate::cli::color::Color;
+use crate::cli::progress;
 use crate::cli::spinner;
 
 pub struct ProgressBar {
@@ -11,7 +12,7 @@ pub struct ProgressBar {
 
 impl ProgressBar {
     pub fn new(message: String, end_message: String) -> Self {
-        let pb = indicatif::ProgressBar::new_spinner();
+        let pb = progress::add(indicatif::ProgressBar::new_spinner());
         pb.set_style(
             ProgressStyle::default_spinner()
                 .tick_strings(spinner::TICKS)
@@ -27,8 +28,8 @@ impl ProgressBar {
     }
 
     pub fn finish(&self) {
-        self.pb.finish_and_clear();
-        println!("{}", &self.end_message);
+        progress::remove(&self.pb);
+        progress::println(&self.end_message);
     }
 
     pub fn set_length(&self, max_length: Option<u64>) {
@@ -0,0 +1,116 @@
+use crate::github::release::Release;
+use serde::{Deserialize, Serialize};
+
+/// Release fetched when no tag is given
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LatestRelease {
+    /// Latest release as returned by the provider, prereleases and drafts are excluded
+    #[default]
+    Stable,
+    /// Newest release, including prereleases
+    IncludePrereleases,
+    /// Newest release of a channel, guessed from the prerelease flag and the tag name
+    Channel(Channel),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    Nightly,
+    Beta,
+    Stable,
+}
+
+const NIGHTLY_MARKERS: [&str; 3] = ["nightly", "canary", "snapshot"];
+const BETA_MARKERS: [&str; 5] = ["beta", "alpha", "rc", "preview", "pre"];
+
+impl LatestRelease {
+    pub fn new(include_prereleases: bool, channel: Option<Channel>) -> Self {
+        match (include_prereleases, channel) {
+            (_, Some(channel)) => LatestRelease::Channel(channel),
+            (true, None) => LatestRelease::IncludePrereleases,
+            (false, None) => LatestRelease::Stable,
+        }
+    }
+
+    /// Whether the release can be the latest one. Drafts never are
+    pub fn matches(&self, release: &Release) -> bool {
+        if release.details.draft {
+            return false;
+        }
+        match self {
+            LatestRelease::Stable => !release.details.prerelease,
+            LatestRelease::IncludePrereleases => true,
+            LatestRelease::Channel(channel) => Channel::of(release) == *channel,
+        }
+    }
+}
+
+impl Channel {
+    fn of(release: &Release) -> Channel {
+        let tag = release.tag.0.to_lowercase();
+        let words = tag
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>();
+        let has_marker = |markers: &[&str]| words.iter().any(|x| markers.contains(x));
+
+        if has_marker(&NIGHTLY_MARKERS) {
+            Channel::Nightly
+        } else if release.details.prerelease || has_marker(&BETA_MARKERS) {
+            Channel::Beta
+        } else {
+            Channel::Stable
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::release::{ReleaseDetails, Tag};
+    use test_case::test_case;
+
+    #[test_case("nightly", false, Channel::Nightly; "nightly tag")]
+    #[test_case("v2.0.0-nightly.20260101", true, Channel::Nightly; "nightly prerelease")]
+    #[test_case("canary", false, Channel::Nightly; "canary")]
+    #[test_case("v2.0.0-rc.1", false, Channel::Beta; "release candidate")]
+    #[test_case("v2.0.0-beta1", false, Channel::Beta; "beta")]
+    #[test_case("v2.0.0", true, Channel::Beta; "prerelease flag")]
+    #[test_case("v2.0.0", false, Channel::Stable; "stable")]
+    #[test_case("2026.01.31", false, Channel::Stable; "calendar version")]
+    #[test_case("v1.0.0-arch", false, Channel::Stable; "word containing a marker")]
+    fn channel_of_release(tag: &str, prerelease: bool, expected: Channel) {
+        let result = Channel::of(&release(tag, prerelease, false));
+
+        assert_eq!(expected, result);
+    }
+
+    #[test_case(LatestRelease::Stable, false, false, true; "stable release")]
+    #[test_case(LatestRelease::Stable, true, false, false; "stable skips prerelease")]
+    #[test_case(LatestRelease::IncludePrereleases, true, false, true; "include prerelease")]
+    #[test_case(LatestRelease::IncludePrereleases, false, true, false; "skip draft")]
+    #[test_case(LatestRelease::Channel(Channel::Beta), true, false, true; "beta channel")]
+    fn latest_release_matches(
+        latest: LatestRelease,
+        prerelease: bool,
+        draft: bool,
+        expected: bool,
+    ) {
+        let result = latest.matches(&release("v1.0.0", prerelease, draft));
+
+        assert_eq!(expected, result);
+    }
+
+    fn release(tag: &str, prerelease: bool, draft: bool) -> Release {
+        Release {
+            tag: Tag(tag.to_string()),
+            assets: vec![],
+            details: ReleaseDetails {
+                prerelease,
+                draft,
+                ..Default::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::github::release::Release;
+use std::time::SystemTime;
+
+/// Filters applied to the releases of a repository
+#[derive(Debug, Default)]
+pub struct ReleaseFilter {
+    /// Only releases published after this date
+    pub since: Option<SystemTime>,
+    /// `Some(true)` only prereleases, `Some(false)` no prereleases
+    pub prerelease: Option<bool>,
+}
+
+impl ReleaseFilter {
+    pub fn new(since: Option<SystemTime>, prerelease: bool, no_prerelease: bool) -> Self {
+        let prerelease = match (prerelease, no_prerelease) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        };
+        Self { since, prerelease }
+    }
+
+    pub fn matches(&self, release: &Release) -> bool {
+        let prerelease = self
+            .prerelease
+            .map(|x| x == release.details.prerelease)
+            .unwrap_or(true);
+        prerelease && !self.is_too_old(release)
+    }
+
+    /// Releases are listed by creation date, not by publish date, so every release must be checked.
+    /// Drafts are never too old since they are not published yet
+    fn is_too_old(&self, release: &Release) -> bool {
+        match (self.since, published_at(release)) {
+            (Some(since), Some(published_at)) => published_at < since,
+            _ => false,
+        }
+    }
+}
+
+fn published_at(release: &Release) -> Option<SystemTime> {
+    release
+        .details
+        .published_at
+        .as_deref()
+        .and_then(|x| humantime::parse_rfc3339_weak(x.trim_end_matches('Z')).ok())
+}
+
+/// Parse a date (`2026-01-31`) or a date and time (`2026-01-31T10:00:00Z`) in UTC
+pub fn parse_date(src: &str) -> Result<SystemTime, String> {
+    let date_time = if src.len() == "YYYY-MM-DD".len() {
+        format!("{}T00:00:00", src)
+    } else {
+        src.trim_end_matches('Z').to_string()
+    };
+    humantime::parse_rfc3339_weak(&date_time)
+        .map_err(|_| format!("Invalid date '{}'. Use YYYY-MM-DD or RFC 3339 format", src))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::release::{ReleaseDetails, Tag};
+    use test_case::test_case;
+
+    #[test_case(None, true; "any")]
+    #[test_case(Some(true), true; "only prereleases")]
+    #[test_case(Some(false), false; "no prereleases")]
+    fn prerelease(prerelease: Option<bool>, expected: bool) {
+        let filter = ReleaseFilter {
+            since: None,
+            prerelease,
+        };
+
+        let result = filter.matches(&release(Some("2026-02-01T00:00:00Z"), true));
+
+        assert_eq!(expected, result);
+    }
+
+    #[test_case("2026-02-01T00:00:00Z", true; "published after")]
+    #[test_case("2026-01-31T23:59:59Z", false; "published before")]
+    fn since(published_at: &str, expected: bool) {
+        let filter = ReleaseFilter {
+            since: Some(parse_date("2026-02-01").unwrap()),
+            prerelease: None,
+        };
+
+        let result = filter.matches(&release(Some(published_at), false));
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn draft_is_never_too_old() {
+        let filter = ReleaseFilter {
+            since: Some(parse_date("2026-02-01").unwrap()),
+            prerelease: None,
+        };
+
+        assert!(!filter.is_too_old(&release(None, false)));
+    }
+
+    #[test_case("2026-01-31"; "date")]
+    #[test_case("2026-01-31T10:00:00Z"; "date time")]
+    fn valid_date(src: &str) {
+        assert!(parse_date(src).is_ok(), "Cannot parse {}", src);
+    }
+
+    #[test]
+    fn invalid_date() {
+        assert!(parse_date("yesterday").is_err());
+    }
+
+    fn release(published_at: Option<&str>, prerelease: bool) -> Release {
+        Release {
+            tag: Tag("v1.0.0".to_string()),
+            assets: vec![],
+            details: ReleaseDetails {
+                name: None,
+                published_at: published_at.map(String::from),
+                prerelease,
+                draft: published_at.is_none(),
+            },
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::cli::color::Color;
+use crate::cli::output::{self, Event};
+use crate::cli::release_filter::ReleaseFilter;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::cli::spinner::Spinner;
+use crate::github::client::{ClientOptions, GithubClient};
+use crate::github::release::Release;
+use crate::github::repository::Repository;
+use serde::Serialize;
+
+pub struct ReleasesHandler {
+    repository: Repository,
+    limit: usize,
+    filter: ReleaseFilter,
+    client_options: ClientOptions,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReleaseOutput {
+    tag: String,
+    name: Option<String>,
+    published_at: Option<String>,
+    prerelease: bool,
+    draft: bool,
+    /// Source code archives are not included
+    assets: Vec<String>,
+}
+
+impl ReleasesHandler {
+    pub fn new(
+        repository: Repository,
+        limit: usize,
+        filter: ReleaseFilter,
+        client_options: ClientOptions,
+    ) -> Self {
+        ReleasesHandler {
+            repository,
+            limit,
+            filter,
+            client_options,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let github = GithubClient::from_environment(&self.client_options);
+        let spinner = Spinner::new("Fetching releases".into(), String::new());
+        spinner.show();
+        let releases = github
+            .releases(&self.repository)
+            .filter(|x| x.as_ref().map(|x| self.filter.matches(x)).unwrap_or(true))
+            .take(self.limit)
+            .map(|x| x.map(ReleaseOutput::from))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                HandlerError::with_kind(
+                    ErrorKind::from(&e),
+                    format!("Error fetching releases: {}", e),
+                )
+            });
+        spinner.finish();
+        let releases = releases?;
+
+        if output::is_json() {
+            output::emit(Event::Releases {
+                releases: &releases,
+            });
+        } else if releases.is_empty() {
+            println!("No releases found");
+        } else {
+            Self::print_table(&releases);
+        }
+        Ok(())
+    }
+
+    fn print_table(releases: &[ReleaseOutput]) {
+        let rows = releases
+            .iter()
+            .map(|x| {
+                [
+                    x.tag.clone(),
+                    x.name.clone().unwrap_or_default(),
+                    x.published_at.clone().unwrap_or_default(),
+                    flags(x),
+                    x.assets.len().to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let header = ["TAG", "NAME", "PUBLISHED", "FLAGS", "ASSETS"];
+        let widths = header
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                rows.iter()
+                    .map(|x| x[i].len())
+                    .max()
+                    .unwrap_or(0)
+                    .max(h.len())
+            })
+            .collect::<Vec<_>>();
+
+        let format_row = |row: &[&str]| {
+            row.iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{:width$}", value, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        println!("{}", Color::new(&format_row(&header)).bold());
+        for row in &rows {
+            println!("{}", format_row(&row.each_ref().map(|x| x.as_str())));
+        }
+    }
+}
+
+fn flags(release: &ReleaseOutput) -> String {
+    [(release.prerelease, "prerelease"), (release.draft, "draft")]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl From<Release> for ReleaseOutput {
+    fn from(release: Release) -> Self {
+        Self {
+            tag: release.tag.0,
+            name: release.details.name,
+            published_at: release.details.published_at,
+            prerelease: release.details.prerelease,
+            draft: release.details.draft,
+            assets: release
+                .assets
+                .into_iter()
+                .filter(|x| !x.is_source_code())
+                .map(|x| x.name)
+                .collect(),
+        }
+    }
+}
@@ -1,17 +1,199 @@
+use crate::checksum::ChecksumMismatch;
+use crate::github::error::GithubError;
+use crate::installer::error::InstallError;
+use crate::system::SystemError;
+use std::fmt::Formatter;
+
 #[derive(Debug)]
 pub enum HandlerError {
-    Default(String),
+    Failure(ErrorKind, String),
     OperationCancelled(String),
+    ChecksumMismatch(ChecksumMismatch),
+    InvalidSignature(String),
 }
 
 pub type HandlerResult = Result<(), HandlerError>;
 
+/// Category of a failure, each one exits with its own code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    General,
+    /// Invalid combination of options or arguments
+    Usage,
+    ChecksumMismatch,
+    InvalidSignature,
+    /// Repository, release or installed tool not found
+    NotFound,
+    NoAssetMatched,
+    /// Invalid credentials or missing permissions
+    Authentication,
+    RateLimit,
+    Network,
+    Install,
+    /// Unknown operating system or architecture
+    System,
+    Cancelled,
+}
+
+impl ErrorKind {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::General => 1,
+            ErrorKind::Usage => 2,
+            ErrorKind::ChecksumMismatch => 3,
+            ErrorKind::NotFound => 4,
+            ErrorKind::NoAssetMatched => 5,
+            ErrorKind::Authentication => 6,
+            ErrorKind::RateLimit => 7,
+            ErrorKind::Network => 8,
+            ErrorKind::Install => 9,
+            ErrorKind::System => 10,
+            ErrorKind::InvalidSignature => 11,
+            ErrorKind::Cancelled => 130,
+        }
+    }
+
+    /// Kind shared by all the errors, or general when they have different kinds
+    pub fn common(kinds: impl IntoIterator<Item = ErrorKind>) -> ErrorKind {
+        let mut kinds = kinds.into_iter();
+        let first = kinds.next().unwrap_or(ErrorKind::General);
+        if kinds.all(|x| x == first) {
+            first
+        } else {
+            ErrorKind::General
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::General => "general",
+            ErrorKind::Usage => "usage",
+            ErrorKind::ChecksumMismatch => "checksum_mismatch",
+            ErrorKind::InvalidSignature => "invalid_signature",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::NoAssetMatched => "no_asset_matched",
+            ErrorKind::Authentication => "authentication",
+            ErrorKind::RateLimit => "rate_limit",
+            ErrorKind::Network => "network",
+            ErrorKind::Install => "install",
+            ErrorKind::System => "system",
+            ErrorKind::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl From<&GithubError> for ErrorKind {
+    fn from(error: &GithubError) -> Self {
+        match error {
+            GithubError::Http(_) => ErrorKind::Network,
+            GithubError::JsonDeserialization(_) => ErrorKind::General,
+            GithubError::RepositoryOrReleaseNotFound => ErrorKind::NotFound,
+            GithubError::RateLimitExceeded { .. }
+            | GithubError::SecondaryRateLimitExceeded { .. } => ErrorKind::RateLimit,
+            GithubError::Forbidden | GithubError::Unauthorized => ErrorKind::Authentication,
+        }
+    }
+}
+
+impl From<&InstallError> for ErrorKind {
+    fn from(_: &InstallError) -> Self {
+        ErrorKind::Install
+    }
+}
+
+impl From<&SystemError> for ErrorKind {
+    fn from(_: &SystemError) -> Self {
+        ErrorKind::System
+    }
+}
+
 impl HandlerError {
     pub fn new(message: String) -> Self {
-        Self::Default(message)
+        Self::Failure(ErrorKind::General, message)
+    }
+
+    pub fn with_kind(kind: ErrorKind, message: String) -> Self {
+        Self::Failure(kind, message)
     }
 
     pub fn op_cancelled(message: &str) -> Self {
         Self::OperationCancelled(message.to_string())
     }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            HandlerError::Failure(kind, _) => *kind,
+            HandlerError::OperationCancelled(_) => ErrorKind::Cancelled,
+            HandlerError::ChecksumMismatch(_) => ErrorKind::ChecksumMismatch,
+            HandlerError::InvalidSignature(_) => ErrorKind::InvalidSignature,
+        }
+    }
+}
+
+impl std::fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandlerError::Failure(_, msg) => f.write_str(msg),
+            HandlerError::OperationCancelled(msg) => f.write_str(msg),
+            HandlerError::ChecksumMismatch(e) => write!(f, "{}", e),
+            HandlerError::InvalidSignature(msg) => f.write_str(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use test_case::test_case;
+
+    #[test_case(GithubError::RepositoryOrReleaseNotFound, ErrorKind::NotFound; "not found")]
+    #[test_case(GithubError::RateLimitExceeded { reset: None }, ErrorKind::RateLimit; "rate limit")]
+    #[test_case(GithubError::SecondaryRateLimitExceeded { retry_after: Some(Duration::from_secs(1)) }, ErrorKind::RateLimit; "secondary rate limit")]
+    #[test_case(GithubError::Unauthorized, ErrorKind::Authentication; "unauthorized")]
+    #[test_case(GithubError::Forbidden, ErrorKind::Authentication; "forbidden")]
+    #[test_case(GithubError::JsonDeserialization("x".into()), ErrorKind::General; "json")]
+    fn github_error_kind(error: GithubError, expected: ErrorKind) {
+        assert_eq!(expected, ErrorKind::from(&error));
+    }
+
+    #[test_case(vec![], ErrorKind::General; "no errors")]
+    #[test_case(vec![ErrorKind::Network, ErrorKind::Network], ErrorKind::Network; "same kind")]
+    #[test_case(vec![ErrorKind::Network, ErrorKind::NotFound], ErrorKind::General; "different kinds")]
+    fn common_kind(kinds: Vec<ErrorKind>, expected: ErrorKind) {
+        assert_eq!(expected, ErrorKind::common(kinds));
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let kinds = all_kinds();
+
+        let mut codes = kinds.iter().map(|x| x.exit_code()).collect::<Vec<_>>();
+        codes.sort();
+        codes.dedup();
+
+        assert_eq!(kinds.len(), codes.len());
+    }
+
+    fn all_kinds() -> Vec<ErrorKind> {
+        std::iter::successors(Some(ErrorKind::General), next_kind).collect()
+    }
+
+    // NOTE: the match doesn't compile when a kind is added, so it can't be left out of all_kinds
+    fn next_kind(kind: &ErrorKind) -> Option<ErrorKind> {
+        match kind {
+            ErrorKind::General => Some(ErrorKind::Usage),
+            ErrorKind::Usage => Some(ErrorKind::ChecksumMismatch),
+            ErrorKind::ChecksumMismatch => Some(ErrorKind::InvalidSignature),
+            ErrorKind::InvalidSignature => Some(ErrorKind::NotFound),
+            ErrorKind::NotFound => Some(ErrorKind::NoAssetMatched),
+            ErrorKind::NoAssetMatched => Some(ErrorKind::Authentication),
+            ErrorKind::Authentication => Some(ErrorKind::RateLimit),
+            ErrorKind::RateLimit => Some(ErrorKind::Network),
+            ErrorKind::Network => Some(ErrorKind::Install),
+            ErrorKind::Install => Some(ErrorKind::System),
+            ErrorKind::System => Some(ErrorKind::Cancelled),
+            ErrorKind::Cancelled => None,
+        }
+    }
 }
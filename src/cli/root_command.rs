@@ -1,8 +1,17 @@
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 use clap::{Parser, ValueHint};
 
+use crate::checksum::Checksum;
+use crate::cli::asset_pattern::AssetPattern;
+use crate::cli::download_handler::DEFAULT_JOBS;
+use crate::cli::output::OutputFormat;
+use crate::cli::release_channel::Channel;
+use crate::cli::release_filter::parse_date;
 use crate::github::repository::Repository;
+use crate::signature::PublicKey;
 
 /// A command line tool to download release assets from GitHub
 ///
@@ -25,6 +34,9 @@ use crate::github::repository::Repository;
 /// Download and install multiple executables from tar/zip archive asset:
 /// $ dra download -s helloworld-many-executables-unix.tar.gz -I helloworld-v2 -I random-script devmatteini/dra-tests
 ///
+/// Download and install all tools listed in a manifest file:
+/// $ dra sync tools.toml
+///
 /// More examples can be found at:
 /// - https://github.com/devmatteini/dra#usage
 /// - https://github.com/devmatteini/dra#examples
@@ -33,17 +45,41 @@ use crate::github::repository::Repository;
 pub struct Cli {
     #[command(subcommand)]
     pub cmd: Command,
+
+    /// Number of times a failed request is retried, with exponential backoff [env: DRA_RETRIES] [default: 3]
+    #[arg(long, global = true, value_name = "N")]
+    pub retries: Option<u32>,
+
+    /// Timeout in seconds of GitHub API requests and of waiting for a download to start [env: DRA_TIMEOUT] [default: 10]
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Wait until the GitHub API rate limit resets and continue, instead of failing [env: DRA_WAIT_FOR_RATE_LIMIT]
+    #[arg(long, global = true)]
+    pub wait_for_rate_limit: bool,
+
+    /// GitHub API URL used for {owner}/{repo} repositories, e.g. https://ghe.example.com/api/v3 [env: DRA_GITHUB_API_URL] [default: https://api.github.com]
+    #[arg(long, global = true, value_name = "URL", value_hint = ValueHint::Url)]
+    pub github_api_url: Option<String>,
+
+    /// Output format. With json, interactive prompts and progress bars are disabled and
+    /// every event (e.g. release resolved, download finished, error) is printed as a JSON line
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
 }
 
 #[derive(Debug, Parser)]
 pub enum Command {
     /// Select and download an asset
     Download {
-        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo} (or https://{enterprise-host}/{owner}/{repo}).
+        /// GitLab repository using format gitlab:{owner}/{repo} or the repository URL https://gitlab.com/{owner}/{repo}.
+        /// Gitea, Forgejo or Codeberg repository using format codeberg:{owner}/{repo} or forgejo:https://{host}/{owner}/{repo}
         #[arg(value_parser = Repository::try_parse)]
         repo: Repository,
 
         /// Select and download the first asset that matches a given pattern.
+        /// Repeat it to download multiple assets (e.g. -s helloworld.tar.gz -s completions.zip).
         ///
         /// Supported patterns are:
         /// - Literal, the exact name of the asset (e.g. helloworld.tar.gz)
@@ -56,21 +92,57 @@ pub enum Command {
             value_name = "PATTERN",
             verbatim_doc_comment
         )]
-        select: Option<String>,
+        select: Vec<String>,
+
+        /// Select and download the first asset whose name matches a regular expression (e.g. "linux.*x86_64").
+        /// {tag} is replaced with the release version before matching. Repeat it to download multiple assets.
+        #[arg(
+            long,
+            group = "non-interactive",
+            value_name = "REGEX",
+            value_parser = AssetPattern::try_parse_regex,
+            verbatim_doc_comment
+        )]
+        select_regex: Vec<String>,
+
+        /// Never select assets matching a pattern (literal, untagged or wildcard).
+        /// Repeat it to exclude more patterns (e.g. --exclude "*musl*" --exclude "*.sha256")
+        #[arg(long, value_name = "PATTERN", verbatim_doc_comment)]
+        exclude: Vec<String>,
+
+        /// Fail when a selection pattern matches more than one asset, instead of selecting the first one
+        #[arg(long)]
+        strict: bool,
 
         /// Automatically select and download an asset based on your operating system and architecture
         #[arg(short, long, group = "non-interactive")]
         automatic: bool,
 
+        /// Select more than one asset in interactive mode (space to select, enter to confirm)
+        #[arg(long, conflicts_with_all = ["non-interactive", "install-feature"])]
+        multiple: bool,
+
         /// Set the tag name for fetching a specific release.
+        /// Use a semver requirement (e.g. ^1.4, ~2, ">=1.2, <2") to fetch the highest matching release,
+        /// prereleases are skipped.
         /// Default value is the latest release
         #[arg(short, long, verbatim_doc_comment)]
         tag: Option<String>,
 
+        /// Fetch the newest release including prereleases, instead of the latest stable release
+        #[arg(long, conflicts_with = "tag")]
+        include_prereleases: bool,
+
+        /// Fetch the newest release of a channel, guessed from the prerelease flag and tag name
+        /// (e.g. nightly, v2.0.0-rc.1)
+        #[arg(long, value_enum, conflicts_with = "tag")]
+        channel: Option<Channel>,
+
         /// Save asset to custom path (file or directory).
         /// Default path is current working directory and the name of the asset.
         /// When used with install feature, it will save the executable to the specified path when applicable.
         /// If you install multiple executables, it must be a directory path.
+        /// If you download multiple assets, it must be a directory path.
         #[arg(short, long, value_hint = ValueHint::AnyPath, verbatim_doc_comment)]
         output: Option<PathBuf>,
 
@@ -102,15 +174,218 @@ pub enum Command {
             verbatim_doc_comment
         )]
         install_file: Option<Vec<String>>,
+
+        /// Verify the downloaded asset against the checksum published in the same release.
+        /// Both per-asset checksum files (e.g. <asset>.sha256) and aggregated ones (e.g. checksums.txt, SHA256SUMS) are supported.
+        ///
+        /// The asset is not saved nor installed when the checksum doesn't match.
+        #[arg(long, verbatim_doc_comment)]
+        verify: bool,
+
+        /// Verify the downloaded asset against the given checksum (sha256:<hex> or sha512:<hex>).
+        ///
+        /// The asset is not saved nor installed when the checksum doesn't match.
+        #[arg(long, value_parser = Checksum::try_parse, verbatim_doc_comment)]
+        checksum: Option<Checksum>,
+
+        /// Verify the signature of the downloaded asset with the given public key.
+        /// The signature is searched in the same release, next to the asset:
+        ///   - minisign public key: <asset>.minisig
+        ///   - GPG public key: <asset>.asc, <asset>.sig or <asset>.gpg (requires gpg)
+        ///   - cosign public key: <asset>.bundle, <asset>.sigstore.json or <asset>.sig (requires cosign)
+        ///
+        /// The asset is not saved nor installed when the signature is not valid.
+        #[arg(
+            long,
+            value_name = "PUBLIC_KEY",
+            value_parser = PublicKey::try_from_file,
+            verbatim_doc_comment
+        )]
+        verify_signature: Option<PublicKey>,
+
+        /// Maximum number of assets downloaded at the same time, when downloading multiple assets
+        #[arg(short, long, default_value_t = NonZeroUsize::new(DEFAULT_JOBS).unwrap())]
+        jobs: NonZeroUsize,
+
+        /// Show the release, the selected assets and where they would be saved, without downloading them.
+        /// With --install the asset is downloaded to a temporary file to show the installer, the archive
+        /// contents and the executables that would be installed, but nothing is written to the destination
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show the assets selected by each pattern for a release, without downloading them
+    Match {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo} (or https://{enterprise-host}/{owner}/{repo}).
+        /// GitLab repository using format gitlab:{owner}/{repo} or the repository URL https://gitlab.com/{owner}/{repo}.
+        /// Gitea, Forgejo or Codeberg repository using format codeberg:{owner}/{repo} or forgejo:https://{host}/{owner}/{repo}
+        #[arg(value_parser = Repository::try_parse)]
+        repo: Repository,
+
+        /// Pattern to resolve (literal, untagged or wildcard). Can be repeated
+        #[arg(
+            short,
+            long,
+            value_name = "PATTERN",
+            required_unless_present = "select_regex"
+        )]
+        select: Vec<String>,
+
+        /// Regular expression to resolve. Can be repeated
+        #[arg(long, value_name = "REGEX", value_parser = AssetPattern::try_parse_regex)]
+        select_regex: Vec<String>,
+
+        /// Never select assets matching a pattern (literal, untagged or wildcard). Can be repeated
+        #[arg(long, value_name = "PATTERN")]
+        exclude: Vec<String>,
+
+        /// Set the tag name of the release. Default value is the latest release
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Fail when a pattern matches more than one asset
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Download an asset to a temporary file and show its contents, without installing it
+    ///
+    /// It shows the file type, the archive files, the executables that could be installed (with their
+    /// architecture) and the metadata of Debian and RPM packages
+    Inspect {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo} (or https://{enterprise-host}/{owner}/{repo}).
+        /// GitLab repository using format gitlab:{owner}/{repo} or the repository URL https://gitlab.com/{owner}/{repo}.
+        /// Gitea, Forgejo or Codeberg repository using format codeberg:{owner}/{repo} or forgejo:https://{host}/{owner}/{repo}
+        #[arg(value_parser = Repository::try_parse)]
+        repo: Repository,
+
+        /// Asset to inspect (literal, untagged or wildcard). When missing, the asset is selected interactively
+        #[arg(short, long, value_name = "PATTERN", conflicts_with = "select_regex")]
+        select: Option<String>,
+
+        /// Regular expression of the asset to inspect
+        #[arg(long, value_name = "REGEX", value_parser = AssetPattern::try_parse_regex)]
+        select_regex: Option<String>,
+
+        /// Set the tag name of the release. Default value is the latest release
+        #[arg(short, long)]
+        tag: Option<String>,
+    },
+
+    /// Install a local file or an asset downloaded from any URL, without fetching a release
+    ///
+    /// Supported assets are the same of `download --install`. Installed files are not recorded,
+    /// so they are not managed by `list`, `update` and `uninstall`.
+    Install {
+        /// Path of a local file or http(s) URL of the asset to install
+        #[arg(value_hint = ValueHint::AnyPath)]
+        source: String,
+
+        /// Save the executable to custom path (file or directory).
+        /// Default path is current working directory.
+        /// If you install multiple executables, it must be a directory path.
+        #[arg(short, long, value_hint = ValueHint::AnyPath, verbatim_doc_comment)]
+        output: Option<PathBuf>,
+
+        /// Select which executable to install from a tar/zip archive.
+        /// You can install multiple executables by specifying `-I/--install-file` multiple times.
+        /// Default is the executable named like the asset (without version), or the only one in the archive.
+        #[arg(short = 'I', long, num_args = 1, verbatim_doc_comment)]
+        install_file: Option<Vec<String>>,
+    },
+
+    /// List the releases of a repository, from newest to oldest
+    Releases {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo} (or https://{enterprise-host}/{owner}/{repo}).
+        /// GitLab repository using format gitlab:{owner}/{repo} or the repository URL https://gitlab.com/{owner}/{repo}.
+        /// Gitea, Forgejo or Codeberg repository using format codeberg:{owner}/{repo} or forgejo:https://{host}/{owner}/{repo}
+        #[arg(value_parser = Repository::try_parse)]
+        repo: Repository,
+
+        /// Maximum number of releases to show
+        #[arg(short, long, default_value_t = 30)]
+        limit: usize,
+
+        /// Only releases published since the given date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", value_parser = parse_date)]
+        since: Option<SystemTime>,
+
+        /// Only show prereleases
+        #[arg(long, conflicts_with = "no_prerelease")]
+        prerelease: bool,
+
+        /// Don't show prereleases
+        #[arg(long)]
+        no_prerelease: bool,
     },
 
     /// Select an asset and generate an untagged version of it
     Untag {
-        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo} (or https://{enterprise-host}/{owner}/{repo}).
+        /// GitLab repository using format gitlab:{owner}/{repo} or the repository URL https://gitlab.com/{owner}/{repo}.
+        /// Gitea, Forgejo or Codeberg repository using format codeberg:{owner}/{repo} or forgejo:https://{host}/{owner}/{repo}
+        #[arg(value_parser = Repository::try_parse)]
+        repo: Repository,
+
+        /// Replace dates in the asset name (e.g. 20240131 or 2024-01-31) with a `*` wildcard.
+        /// The generated pattern may match more than one asset
+        #[arg(long)]
+        wildcard_dates: bool,
+    },
+
+    /// Download and install all tools listed in a manifest file
+    ///
+    /// The manifest is a TOML file with a `[[tool]]` entry for each repository:
+    ///
+    /// [[tool]]
+    /// repo = "devmatteini/dra-tests"         # required, same format of `download <REPO>`
+    /// select = "helloworld_{tag}.tar.gz"     # optional, same as `--select`. Default is automatic selection
+    /// tag = "0.1.0"                          # optional, same as `--tag`. Default is the latest release
+    /// output = "/usr/local/bin"              # optional, same as `--output`
+    /// install = true                         # optional, same as `--install`. Default is false
+    /// install_file = ["helloworld"]          # optional, same as `--install-file`
+    /// verify = true                          # optional, same as `--verify`. Default is false
+    /// checksum = "sha256:<HEX>"              # optional, same as `--checksum`
+    /// verify_signature = "keys/minisign.pub" # optional, same as `--verify-signature`, relative to the manifest
+    ///
+    /// Every tool is synced even if some of them fail, and a summary is printed at the end.
+    #[command(verbatim_doc_comment)]
+    Sync {
+        /// Path to the manifest file
+        #[arg(value_hint = ValueHint::FilePath)]
+        manifest: PathBuf,
+
+        /// Maximum number of tools synced at the same time
+        #[arg(short, long, default_value_t = NonZeroUsize::new(DEFAULT_JOBS).unwrap())]
+        jobs: NonZeroUsize,
+    },
+
+    /// List tools installed with dra
+    List,
+
+    /// Remove the files installed from a repository
+    Uninstall {
+        /// GitHub repository using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo} (or https://{enterprise-host}/{owner}/{repo}).
+        /// GitLab repository using format gitlab:{owner}/{repo} or the repository URL https://gitlab.com/{owner}/{repo}.
+        /// Gitea, Forgejo or Codeberg repository using format codeberg:{owner}/{repo} or forgejo:https://{host}/{owner}/{repo}
         #[arg(value_parser = Repository::try_parse)]
         repo: Repository,
     },
 
+    /// List installed tools with a newer release available
+    Outdated,
+
+    /// Update installed tools to their latest release
+    ///
+    /// The asset is selected and installed with the same options used for the first installation.
+    /// Tools already up to date are skipped.
+    Update {
+        /// GitHub repositories to update using format {owner}/{repo} or the repository URL https://github.com/{owner}/{repo}.
+        /// Default is all installed tools
+        #[arg(value_parser = Repository::try_parse, verbatim_doc_comment)]
+        repos: Vec<Repository>,
+    },
+
     /// Generate shell completion
     Completion {
         /// Shell to generate completion for
@@ -1,7 +1,8 @@
-use crate::cli::result::HandlerError;
+use crate::cli::output;
+use crate::cli::result::{ErrorKind, HandlerError};
 use crate::github::release::Asset;
-use dialoguer::Select;
 use dialoguer::theme::ColorfulTheme;
+use dialoguer::{MultiSelect, Select};
 
 pub struct Messages<'a> {
     pub select_prompt: &'a str,
@@ -9,8 +10,10 @@ pub struct Messages<'a> {
 }
 
 pub type AskSelectAssetResult = Result<Asset, HandlerError>;
+pub type AskSelectAssetsResult = Result<Vec<Asset>, HandlerError>;
 
 pub fn ask_select_asset(assets: Vec<Asset>, messages: Messages) -> AskSelectAssetResult {
+    check_interactive()?;
     let items = assets_names(&assets);
     let index = Select::with_theme(&ColorfulTheme::default())
         .with_prompt(messages.select_prompt)
@@ -25,6 +28,35 @@ pub fn ask_select_asset(assets: Vec<Asset>, messages: Messages) -> AskSelectAsse
     Ok(find_asset_by_name(selected_name, assets))
 }
 
+/// Select one or more assets (space to toggle, enter to confirm)
+pub fn ask_select_assets(assets: Vec<Asset>, messages: Messages) -> AskSelectAssetsResult {
+    check_interactive()?;
+    let items = assets_names(&assets);
+    let indexes = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(messages.select_prompt)
+        .items(&items)
+        .interact_opt()
+        .map_err(|e| HandlerError::new(e.to_string()))?
+        .filter(|x| !x.is_empty())
+        .ok_or_else(|| HandlerError::op_cancelled(messages.quit_select))?;
+    let selected_names = indexes.into_iter().map(|i| &items[i]).collect::<Vec<_>>();
+    Ok(assets
+        .into_iter()
+        .filter(|x| selected_names.iter().any(|name| x.is_same_name(name)))
+        .collect())
+}
+
+/// Prompts would mix with the JSON events on stdout
+fn check_interactive() -> Result<(), HandlerError> {
+    if output::is_json() {
+        return Err(HandlerError::with_kind(
+            ErrorKind::Usage,
+            "Interactive selection is not available with --output-format json, use --select or --automatic".into(),
+        ));
+    }
+    Ok(())
+}
+
 fn assets_names(assets: &[Asset]) -> Vec<String> {
     assets.iter().map(|x| x.show_name().to_string()).collect()
 }
@@ -0,0 +1,83 @@
+use crate::cli::color::Color;
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
+use crate::cli::result::{ErrorKind, HandlerError};
+use crate::github::client::GithubClient;
+use crate::github::release::Asset;
+use crate::signature::{PublicKey, expected_signature_names, find_signature_asset};
+use crate::{signature, temp_file};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Verify the signature of the downloaded asset with the signature published in the same release.
+/// The downloaded file is deleted when the signature is not valid
+pub fn verify_signature(
+    github: &GithubClient,
+    key: Option<&PublicKey>,
+    asset: &Asset,
+    release_assets: &[Asset],
+    path: &Path,
+) -> Result<(), HandlerError> {
+    let key = match key {
+        Some(x) => x,
+        None => return Ok(()),
+    };
+
+    let result = find_signature_asset(key, &asset.name, release_assets)
+        .ok_or_else(|| {
+            HandlerError::InvalidSignature(format!(
+                "No {} signature found for {} in this release (expected one of: {})",
+                key.kind(),
+                asset.name,
+                expected_signature_names(key, &asset.name).join(", ")
+            ))
+        })
+        .and_then(|signature_asset| {
+            let signature_path = download_signature(github, signature_asset)?;
+            let result = signature::verify(key, path, &signature_path).map_err(|e| {
+                HandlerError::InvalidSignature(format!(
+                    "Invalid signature for {}\n{}",
+                    asset.name, e
+                ))
+            });
+            let _ = std::fs::remove_file(&signature_path);
+            result
+        });
+
+    match result {
+        Ok(_) => {
+            progress::println(&format!(
+                "{}",
+                Color::new(&format!("Signature verified ({})", key.kind())).green()
+            ));
+            output::emit(Event::SignatureVerified {
+                asset: &asset.name,
+                kind: key.kind(),
+            });
+            Ok(())
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(path);
+            Err(e)
+        }
+    }
+}
+
+fn download_signature(github: &GithubClient, asset: &Asset) -> Result<PathBuf, HandlerError> {
+    let (mut stream, _) = github.download_asset_stream(asset).map_err(|e| {
+        HandlerError::with_kind(
+            ErrorKind::from(&e),
+            format!("Error downloading signature {}: {}", asset.name, e),
+        )
+    })?;
+
+    // NOTE: keep the asset name, cosign needs the extension to tell bundles from detached signatures
+    let path = temp_file::temp_file().with_extension(&asset.name);
+    File::create(&path)
+        .and_then(|mut file| std::io::copy(&mut stream, &mut file))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&path);
+            HandlerError::new(format!("Error saving signature {}: {}", asset.name, e))
+        })?;
+    Ok(path)
+}
@@ -3,6 +3,7 @@ use std::time::Duration;
 use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::cli::color::Color;
+use crate::cli::progress;
 
 // NOTE: spinner ticks and duration are from
 // https://github.com/sindresorhus/cli-spinners/blob/00de8fbeee16fa49502fa4f687449f70f2c8ca2c/spinners.json#L2-L16
@@ -17,7 +18,7 @@ pub struct Spinner {
 
 impl Spinner {
     pub fn new(message: String, end_message: String) -> Self {
-        let pb = ProgressBar::new_spinner();
+        let pb = progress::add(ProgressBar::new_spinner());
         pb.set_style(
             ProgressStyle::default_spinner()
                 .tick_strings(TICKS)
@@ -32,14 +33,13 @@ impl Spinner {
         self.pb.enable_steady_tick(TICK_DURATION);
     }
 
-    #[allow(dead_code)]
     pub fn finish(&self) {
-        self.pb.finish_and_clear();
+        progress::remove(&self.pb);
     }
 
     pub fn finish_with_message(&self, message: &str) {
-        self.pb.finish_and_clear();
-        println!("{}", message);
+        progress::remove(&self.pb);
+        progress::println(message);
     }
 
     #[allow(dead_code)]
@@ -54,6 +54,10 @@ impl Spinner {
         )
     }
 
+    pub fn inspect_layout() -> Spinner {
+        Spinner::new("Inspecting".into(), String::new())
+    }
+
     pub fn empty_layout() -> Spinner {
         Spinner::new(String::new(), String::new())
     }
@@ -0,0 +1,126 @@
+use crate::cli::asset_pattern::AssetPattern;
+use crate::cli::color::Color;
+use crate::cli::download_handler::DownloadHandler;
+use crate::cli::manifest::{Manifest, ManifestTool};
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::cli::worker_pool;
+use crate::github::client::{ClientOptions, GithubClient};
+use crate::github::repository::Repository;
+use std::path::PathBuf;
+
+pub struct SyncHandler {
+    manifest: PathBuf,
+    /// Maximum number of tools synced at the same time
+    jobs: usize,
+    client_options: ClientOptions,
+}
+
+struct SyncResult {
+    repository: Repository,
+    result: HandlerResult,
+}
+
+impl SyncHandler {
+    pub fn new(manifest: PathBuf, jobs: usize, client_options: ClientOptions) -> Self {
+        SyncHandler {
+            manifest,
+            jobs,
+            client_options,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let manifest = Manifest::from_file(&self.manifest).map_err(HandlerError::new)?;
+        let github = GithubClient::from_environment(&self.client_options);
+
+        let results = worker_pool::run(&manifest.tools, self.jobs, |tool| {
+            Self::sync_tool(&github, tool.clone())
+        });
+
+        if output::is_json() {
+            Self::emit_summary(&results);
+        } else {
+            println!();
+            Self::print_summary(&results);
+        }
+
+        let failures = results
+            .iter()
+            .filter_map(|x| x.result.as_ref().err().map(HandlerError::kind))
+            .collect::<Vec<_>>();
+        if !failures.is_empty() {
+            return Err(HandlerError::with_kind(
+                ErrorKind::common(failures.iter().copied()),
+                format!(
+                    "{} of {} tools failed to sync",
+                    failures.len(),
+                    results.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn sync_tool(github: &GithubClient, tool: ManifestTool) -> SyncResult {
+        progress::println(&format!(
+            "{}",
+            Color::new(&format!("Syncing {}", tool.repository)).bold()
+        ));
+        let repository = tool.repository.clone();
+        // NOTE: sync is non-interactive, so tools without a selection pattern are automatically selected
+        let automatic = tool.select.is_none();
+        let handler = DownloadHandler::new(
+            tool.repository,
+            tool.select
+                .into_iter()
+                .map(AssetPattern::Wildcard)
+                .collect(),
+            automatic,
+            tool.tag,
+            tool.output,
+            tool.install,
+            tool.install_file,
+        )
+        .with_verification(tool.verify, tool.checksum)
+        .with_signature_key(tool.verify_signature);
+        let result = handler.run_with(github);
+        if let Err(e) = &result {
+            progress::eprintln(&format!(
+                "{}",
+                Color::new(&format!("{}: {}", repository, e)).red()
+            ));
+        }
+        SyncResult { repository, result }
+    }
+
+    fn emit_summary(results: &[SyncResult]) {
+        for x in results {
+            output::emit(Event::ToolSynced {
+                repository: x.repository.to_string(),
+                success: x.result.is_ok(),
+                message: x.result.as_ref().err().map(|e| e.to_string()),
+            });
+        }
+    }
+
+    fn print_summary(results: &[SyncResult]) {
+        println!("{}", Color::new("Sync summary:").bold());
+        for x in results {
+            match &x.result {
+                Ok(_) => println!("{} {}", Color::new("✔").green(), x.repository),
+                Err(e) => println!(
+                    "{} {}: {}",
+                    Color::new("✘").red(),
+                    x.repository,
+                    first_line(&e.to_string())
+                ),
+            }
+        }
+    }
+}
+
+fn first_line(message: &str) -> String {
+    message.lines().next().unwrap_or_default().to_string()
+}
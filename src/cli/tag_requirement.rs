@@ -0,0 +1,113 @@
+use crate::github::release::{Release, Tag};
+use semver::{Version, VersionReq};
+
+/// Semver requirement used as `--tag` (e.g. `^1.4`, `~2`, `>=1.2, <2`) to select the highest matching release
+#[derive(Debug)]
+pub struct TagRequirement(VersionReq);
+
+impl TagRequirement {
+    /// Only tags starting with a semver operator are requirements, any other tag is an exact tag name
+    pub fn try_parse(tag: &Tag) -> Result<Option<TagRequirement>, String> {
+        if !tag.0.starts_with(['^', '~', '<', '>', '=', '*']) {
+            return Ok(None);
+        }
+        VersionReq::parse(&tag.0)
+            .map(|x| Some(TagRequirement(x)))
+            .map_err(|e| format!("Invalid tag requirement '{}': {}", tag.0, e))
+    }
+
+    /// Version of the release if it satisfies the requirement. Prereleases and drafts never match
+    pub fn matches(&self, release: &Release) -> Option<Version> {
+        if release.details.prerelease || release.details.draft {
+            return None;
+        }
+        Version::parse(&release.tag.without_prefix())
+            .ok()
+            .filter(|x| x.pre.is_empty() && self.0.matches(x))
+    }
+
+    /// Highest release satisfying the requirement
+    pub fn select<E>(
+        &self,
+        releases: impl Iterator<Item = Result<Release, E>>,
+    ) -> Result<Option<Release>, E> {
+        let mut highest: Option<(Version, Release)> = None;
+        for release in releases {
+            let release = release?;
+            if let Some(version) = self.matches(&release)
+                && highest.as_ref().is_none_or(|(x, _)| version > *x)
+            {
+                highest = Some((version, release));
+            }
+        }
+        Ok(highest.map(|(_, release)| release))
+    }
+}
+
+impl std::fmt::Display for TagRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::release::ReleaseDetails;
+    use test_case::test_case;
+
+    #[test_case("1.4.0"; "exact")]
+    #[test_case("v1.4.0"; "leading v")]
+    #[test_case("latest"; "name")]
+    fn not_a_requirement(tag: &str) {
+        let result = TagRequirement::try_parse(&Tag(tag.to_string()));
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    fn invalid_requirement() {
+        let result = TagRequirement::try_parse(&Tag("^foo".to_string()));
+
+        assert!(result.is_err());
+    }
+
+    #[test_case("^1.4", &["1.3.0", "v1.4.2", "1.9.0", "2.0.0"], Some("1.9.0"); "caret")]
+    #[test_case("~1.4", &["1.4.1", "v1.4.3", "1.5.0"], Some("v1.4.3"); "tilde")]
+    #[test_case(">=1.2, <2", &["2.1.0", "1.2.0", "foo"], Some("1.2.0"); "range")]
+    #[test_case("^3", &["1.0.0", "2.0.0"], None; "no match")]
+    #[test_case("^1", &["1.1.0", "1.2.0-rc.1"], Some("1.1.0"); "skip prerelease version")]
+    fn select_highest(requirement: &str, tags: &[&str], expected: Option<&str>) {
+        let requirement = TagRequirement::try_parse(&Tag(requirement.to_string()))
+            .unwrap()
+            .unwrap();
+        let releases = tags.iter().map(|x| Ok::<_, ()>(release(x, false)));
+
+        let result = requirement.select(releases).unwrap();
+
+        assert_eq!(expected, result.as_ref().map(|x| x.tag.0.as_str()));
+    }
+
+    #[test]
+    fn skip_prerelease() {
+        let requirement = TagRequirement::try_parse(&Tag("^1".to_string()))
+            .unwrap()
+            .unwrap();
+        let releases = [release("1.1.0", false), release("1.2.0", true)];
+
+        let result = requirement.select(releases.into_iter().map(Ok::<_, ()>));
+
+        assert_eq!("1.1.0", result.unwrap().unwrap().tag.0);
+    }
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag: Tag(tag.to_string()),
+            assets: vec![],
+            details: ReleaseDetails {
+                prerelease,
+                ..Default::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::cli::color::Color;
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::github::repository::Repository;
+use crate::state;
+use crate::state::InstallState;
+use std::io;
+use std::path::Path;
+
+pub struct UninstallHandler {
+    repository: Repository,
+}
+
+impl UninstallHandler {
+    pub fn new(repository: Repository) -> Self {
+        UninstallHandler { repository }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let path = state::state_file().map_err(HandlerError::new)?;
+        let _lock = InstallState::lock(&path).map_err(HandlerError::new)?;
+        let mut install_state = InstallState::load(&path).map_err(HandlerError::new)?;
+        let repository = self.repository.to_string();
+
+        let tool = install_state.remove(&repository).ok_or_else(|| {
+            HandlerError::with_kind(
+                ErrorKind::NotFound,
+                format!("{} is not installed with dra", repository),
+            )
+        })?;
+
+        if tool.files.is_empty() && !output::is_json() {
+            println!(
+                "No files to remove: {} was installed by a package manager (dpkg/rpm), use it to uninstall the package",
+                tool.asset
+            );
+        }
+        for file in &tool.files {
+            remove_file(file)?;
+            progress::println(&format!("Removed {}", file.display()));
+        }
+
+        install_state.save(&path).map_err(HandlerError::new)?;
+        progress::println(&format!(
+            "{}",
+            Color::new(&format!("{} uninstalled!", repository)).green()
+        ));
+        output::emit(Event::ToolUninstalled {
+            repository: &repository,
+            files: &tool.files,
+        });
+        Ok(())
+    }
+}
+
+fn remove_file(path: &Path) -> Result<(), HandlerError> {
+    match std::fs::remove_file(path) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(HandlerError::new(format!(
+            "Unable to remove {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
@@ -1,27 +1,52 @@
 use crate::cli::github_release::{check_has_assets, fetch_release_for};
+use crate::cli::output::{self, Event};
+use crate::cli::release_channel::LatestRelease;
 use crate::cli::result::{HandlerError, HandlerResult};
 use crate::cli::select_assets;
-use crate::github::client::GithubClient;
+use crate::github::client::{ClientOptions, GithubClient};
 use crate::github::release::{Asset, Release};
 use crate::github::repository::Repository;
 use crate::github::tagged_asset::TaggedAsset;
 
 pub struct UntagHandler {
     repository: Repository,
+    client_options: ClientOptions,
+    wildcard_dates: bool,
 }
 
 impl UntagHandler {
-    pub fn new(repository: Repository) -> Self {
-        UntagHandler { repository }
+    pub fn new(repository: Repository, client_options: ClientOptions) -> Self {
+        UntagHandler {
+            repository,
+            client_options,
+            wildcard_dates: false,
+        }
+    }
+
+    pub fn with_wildcard_dates(mut self, wildcard_dates: bool) -> Self {
+        self.wildcard_dates = wildcard_dates;
+        self
     }
 
     pub fn run(&self) -> HandlerResult {
-        let github = GithubClient::from_environment();
+        let github = GithubClient::from_environment(&self.client_options);
         let release = Self::fetch_latest_release(&github, &self.repository)?;
         check_has_assets(&release)?;
         let selected_asset = Self::ask_select_asset(release.assets)?;
         let untagged = TaggedAsset::untag(&release.tag, &selected_asset);
-        println!("{}", untagged);
+        let untagged = if self.wildcard_dates {
+            TaggedAsset::wildcard_dates(&untagged)
+        } else {
+            untagged
+        };
+        if output::is_json() {
+            output::emit(Event::Untagged {
+                asset: &selected_asset.name,
+                pattern: &untagged,
+            });
+        } else {
+            println!("{}", untagged);
+        }
         Ok(())
     }
 
@@ -29,7 +54,7 @@ impl UntagHandler {
         github: &GithubClient,
         repository: &Repository,
     ) -> Result<Release, HandlerError> {
-        fetch_release_for(github, repository, None)
+        fetch_release_for(github, repository, None, LatestRelease::Stable)
     }
 
     fn ask_select_asset(assets: Vec<Asset>) -> select_assets::AskSelectAssetResult {
@@ -0,0 +1,302 @@
+use crate::cli::asset_pattern::AssetPattern;
+use crate::cli::color::Color;
+use crate::cli::download_handler::DownloadHandler;
+use crate::cli::installed_tools::{
+    compare_versions, fetch_latest_release, load_install_state, warn_not_comparable,
+};
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
+use crate::github::client::{ClientOptions, GithubClient};
+use crate::github::repository::Repository;
+use crate::signature::PublicKey;
+use crate::state::{
+    InstallState, InstalledSelection, InstalledSignature, InstalledTool, InstalledVerification,
+};
+use std::cmp::Ordering;
+
+pub struct UpdateHandler {
+    repositories: Vec<Repository>,
+    client_options: ClientOptions,
+}
+
+impl UpdateHandler {
+    pub fn new(repositories: Vec<Repository>, client_options: ClientOptions) -> Self {
+        UpdateHandler {
+            repositories,
+            client_options,
+        }
+    }
+
+    pub fn run(&self) -> HandlerResult {
+        let (_, install_state) = load_install_state()?;
+        let tools = self.tools_to_update(&install_state)?;
+        if tools.is_empty() {
+            progress::println("No tools installed with dra");
+            return Ok(());
+        }
+
+        let github = GithubClient::from_environment(&self.client_options);
+        let failures = tools
+            .iter()
+            .filter_map(|tool| Self::update_tool(&github, tool).err())
+            .map(|e| e.kind())
+            .collect::<Vec<_>>();
+
+        if !failures.is_empty() {
+            return Err(HandlerError::with_kind(
+                ErrorKind::common(failures.iter().copied()),
+                format!(
+                    "{} of {} tools failed to update",
+                    failures.len(),
+                    tools.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn tools_to_update<'a>(
+        &self,
+        install_state: &'a InstallState,
+    ) -> Result<Vec<&'a InstalledTool>, HandlerError> {
+        if self.repositories.is_empty() {
+            return Ok(install_state.tools().iter().collect());
+        }
+
+        self.repositories
+            .iter()
+            .map(|repository| {
+                let repository = repository.to_string();
+                install_state.find(&repository).ok_or_else(|| {
+                    HandlerError::with_kind(
+                        ErrorKind::NotFound,
+                        format!("{} is not installed with dra", repository),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn update_tool(github: &GithubClient, tool: &InstalledTool) -> HandlerResult {
+        let result = Self::try_update_tool(github, tool);
+        if let Err(e) = &result {
+            progress::eprintln(&format!(
+                "{}",
+                Color::new(&format!("{}: {}", tool.repository, e)).red()
+            ));
+        }
+        result
+    }
+
+    fn try_update_tool(github: &GithubClient, tool: &InstalledTool) -> HandlerResult {
+        let (repository, release) = fetch_latest_release(github, tool)?;
+        match compare_versions(&tool.tag, &release.tag.0) {
+            Some(Ordering::Greater) => {}
+            Some(_) => {
+                progress::println(&format!("{} is up to date ({})", tool.repository, tool.tag));
+                output::emit(Event::ToolUpToDate {
+                    repository: &tool.repository,
+                    tag: &tool.tag,
+                });
+                return Ok(());
+            }
+            None => {
+                warn_not_comparable(&tool.repository, &tool.tag, &release.tag.0);
+                return Ok(());
+            }
+        }
+        let (verify, signature_key) = verification(tool)?;
+
+        progress::println(&format!(
+            "{}",
+            Color::new(&format!(
+                "Updating {} from {} to {}",
+                tool.repository, tool.tag, release.tag.0
+            ))
+            .bold()
+        ));
+        output::emit(Event::ToolUpdating {
+            repository: &tool.repository,
+            installed_tag: &tool.tag,
+            latest_tag: &release.tag.0,
+        });
+        let (select, automatic) = asset_selection(&tool.selection);
+        DownloadHandler::new(
+            repository,
+            select,
+            automatic,
+            None,
+            Some(tool.destination.clone()),
+            true,
+            tool.install_files.clone(),
+        )
+        .with_exclude(tool.exclude.clone().unwrap_or_default())
+        .with_verification(verify, None)
+        .with_signature_key(signature_key)
+        .run_for(github, release)
+    }
+}
+
+/// Asset patterns, or automatic selection, the tool was installed with
+fn asset_selection(selection: &InstalledSelection) -> (Vec<AssetPattern>, bool) {
+    match selection {
+        InstalledSelection::Automatic => (vec![], true),
+        InstalledSelection::Pattern(pattern) => {
+            (vec![AssetPattern::Wildcard(pattern.clone())], false)
+        }
+        InstalledSelection::Regex(pattern) => (vec![AssetPattern::Regex(pattern.clone())], false),
+    }
+}
+
+/// Checksum verification and public key the tool was installed with.
+/// Tools whose verification cannot be repeated on a newer release are not updated
+fn verification(tool: &InstalledTool) -> Result<(bool, Option<PublicKey>), HandlerError> {
+    let verify = match tool.verification {
+        InstalledVerification::Disabled => false,
+        InstalledVerification::Release => true,
+        InstalledVerification::Pinned => {
+            return Err(not_updatable(
+                "it was installed with --checksum, which only matches the installed release",
+                "--checksum <CHECKSUM>",
+            ));
+        }
+    };
+    let signature_key = match &tool.signature {
+        None => None,
+        Some(InstalledSignature::Key(path)) => Some(
+            PublicKey::try_from_file(&path.to_string_lossy())
+                .map_err(|e| not_updatable(&e, "--verify-signature <PUBLIC_KEY>"))?,
+        ),
+        Some(InstalledSignature::Unrecorded) => {
+            return Err(not_updatable(
+                "the public key used to verify the signature was not recorded",
+                "--verify-signature <PUBLIC_KEY>",
+            ));
+        }
+    };
+    Ok((verify, signature_key))
+}
+
+fn not_updatable(reason: &str, option: &str) -> HandlerError {
+    HandlerError::with_kind(
+        ErrorKind::Usage,
+        format!(
+            "Unable to update automatically: {}. Use `dra download --install {}` instead",
+            reason, option
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::tests::fixture;
+    use std::path::PathBuf;
+    use test_case::test_case;
+
+    #[test_case(InstalledSelection::Automatic, vec![], true; "automatic")]
+    #[test_case(
+        InstalledSelection::Pattern("foo-{tag}-linux.tar.gz".into()),
+        vec![AssetPattern::Wildcard("foo-{tag}-linux.tar.gz".into())],
+        false;
+        "pattern"
+    )]
+    #[test_case(
+        InstalledSelection::Regex("^foo-.*-linux".into()),
+        vec![AssetPattern::Regex("^foo-.*-linux".into())],
+        false;
+        "regex"
+    )]
+    fn select_assets_as_installed(
+        selection: InstalledSelection,
+        expected_select: Vec<AssetPattern>,
+        expected_automatic: bool,
+    ) {
+        let (select, automatic) = asset_selection(&selection);
+
+        assert_eq!(expected_select, select);
+        assert_eq!(expected_automatic, automatic);
+    }
+
+    #[test_case(InstalledVerification::Disabled, false; "disabled")]
+    #[test_case(InstalledVerification::Release, true; "release checksum")]
+    fn checksum_verification(verification: InstalledVerification, expected: bool) {
+        let tool = InstalledTool {
+            verification,
+            ..tool()
+        };
+
+        let (verify, _) = verification_of(&tool);
+
+        assert_eq!(expected, verify);
+    }
+
+    #[test]
+    fn pinned_checksum_is_not_updatable() {
+        let tool = InstalledTool {
+            verification: InstalledVerification::Pinned,
+            ..tool()
+        };
+
+        let result = verification(&tool);
+
+        assert!(matches!(
+            result,
+            Err(HandlerError::Failure(ErrorKind::Usage, _))
+        ));
+    }
+
+    #[test]
+    fn signature_key() {
+        let path = fixture("minisign.pub");
+        let tool = InstalledTool {
+            signature: Some(InstalledSignature::Key(path.clone())),
+            ..tool()
+        };
+
+        let (_, key) = verification_of(&tool);
+
+        assert_eq!(Some(PublicKey::Minisign(path)), key);
+    }
+
+    #[test_case(InstalledSignature::Key(PathBuf::from("/missing/minisign.pub")); "missing key")]
+    #[test_case(InstalledSignature::Unrecorded; "unrecorded key")]
+    fn signature_key_is_not_updatable(signature: InstalledSignature) {
+        let tool = InstalledTool {
+            signature: Some(signature),
+            ..tool()
+        };
+
+        let result = verification(&tool);
+
+        assert!(matches!(
+            result,
+            Err(HandlerError::Failure(ErrorKind::Usage, _))
+        ));
+    }
+
+    fn verification_of(tool: &InstalledTool) -> (bool, Option<PublicKey>) {
+        verification(tool).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn tool() -> InstalledTool {
+        InstalledTool {
+            repository: "foo/bar".to_string(),
+            tag: "v1.0.0".to_string(),
+            asset: "ANY_ASSET".to_string(),
+            files: vec![PathBuf::from("/bin/bar")],
+            sha256: "ANY_SHA256".to_string(),
+            installed_at: "2026-01-01T00:00:00Z".to_string(),
+            selection: InstalledSelection::Automatic,
+            destination: PathBuf::from("/bin"),
+            install_files: None,
+            exclude: None,
+            tag_requirement: None,
+            include_prereleases: false,
+            channel: None,
+            verification: InstalledVerification::Disabled,
+            signature: None,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Run `job` for every item using at most `workers` threads.
+/// Results are in the same order of the items
+pub fn run<'a, T, R, F>(items: &'a [T], workers: usize, job: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&'a T) -> R + Sync,
+{
+    if workers <= 1 || items.len() <= 1 {
+        return items.iter().map(job).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<Option<R>>>());
+    std::thread::scope(|scope| {
+        for _ in 0..workers.min(items.len()) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result = job(item);
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|x| x.expect("every item has a result"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use test_case::test_case;
+
+    #[test_case(1; "sequential")]
+    #[test_case(3; "parallel")]
+    #[test_case(20; "more workers than items")]
+    fn results_in_items_order(workers: usize) {
+        let items = (0..10).collect::<Vec<u64>>();
+
+        let result = run(&items, workers, |x| {
+            std::thread::sleep(Duration::from_millis(10 - x));
+            x * 2
+        });
+
+        assert_eq!((0..10).map(|x| x * 2).collect::<Vec<_>>(), result);
+    }
+
+    #[test]
+    fn at_most_workers_run_concurrently() {
+        let items = (0..12).collect::<Vec<_>>();
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        run(&items, 3, |_| {
+            let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(current, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(10));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        assert!(max_running.load(Ordering::SeqCst) <= 3);
+    }
+}
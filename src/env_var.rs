@@ -16,3 +16,7 @@ pub fn string(name: &str) -> Option<String> {
         .ok()
         .and_then(|x| if x.is_empty() { None } else { Some(x) })
 }
+
+pub fn number<T: std::str::FromStr>(name: &str) -> Option<T> {
+    string(name).and_then(|x| x.trim().parse().ok())
+}
@@ -0,0 +1,98 @@
+use crate::env_var;
+use crate::github::constants::{
+    DRA_CODEBERG_TOKEN, DRA_GITEA_TOKEN, DRA_GITHUB_TOKEN, DRA_GITLAB_TOKEN, GH_ENTERPRISE_TOKEN,
+    GH_TOKEN, GITHUB_ENTERPRISE_TOKEN, GITHUB_TOKEN, GITLAB_TOKEN,
+};
+use std::process::Command;
+
+/// Find the GitHub authentication token for the given host.
+///
+/// github.com uses `DRA_GITHUB_TOKEN`, `GITHUB_TOKEN` and `GH_TOKEN`.
+/// Any other host (including the one configured with `--github-api-url`) uses `DRA_GITHUB_TOKEN_<HOST>` (e.g. `DRA_GITHUB_TOKEN_GHE_EXAMPLE_COM`),
+/// `GH_ENTERPRISE_TOKEN` and `GITHUB_ENTERPRISE_TOKEN`, so tokens are never sent to the wrong host.
+///
+/// The GitHub cli token is used when no environment variable is set.
+pub fn github_token(host: &str, is_default_host: bool) -> Option<String> {
+    let from_env = if is_default_host {
+        env_var::string(DRA_GITHUB_TOKEN)
+            .or_else(|| env_var::string(GITHUB_TOKEN))
+            .or_else(|| env_var::string(GH_TOKEN))
+    } else {
+        env_var::string(&host_token_env_var(DRA_GITHUB_TOKEN, host))
+            .or_else(|| env_var::string(GH_ENTERPRISE_TOKEN))
+            .or_else(|| env_var::string(GITHUB_ENTERPRISE_TOKEN))
+    };
+    from_env.or_else(|| github_cli_token(host))
+}
+
+/// Find the GitLab authentication token for the given host.
+///
+/// gitlab.com uses `DRA_GITLAB_TOKEN` and `GITLAB_TOKEN`,
+/// self-hosted instances use `DRA_GITLAB_TOKEN_<HOST>` (e.g. `DRA_GITLAB_TOKEN_GITLAB_EXAMPLE_COM`).
+pub fn gitlab_token(host: &str, is_default_host: bool) -> Option<String> {
+    if is_default_host {
+        env_var::string(DRA_GITLAB_TOKEN).or_else(|| env_var::string(GITLAB_TOKEN))
+    } else {
+        env_var::string(&host_token_env_var(DRA_GITLAB_TOKEN, host))
+    }
+}
+
+/// Find the Gitea (or Forgejo) authentication token for the given host.
+///
+/// Every instance uses `DRA_GITEA_TOKEN_<HOST>` (e.g. `DRA_GITEA_TOKEN_CODEBERG_ORG`),
+/// Codeberg can also use `DRA_CODEBERG_TOKEN`.
+pub fn gitea_token(host: &str, is_default_host: bool) -> Option<String> {
+    env_var::string(&host_token_env_var(DRA_GITEA_TOKEN, host)).or_else(|| {
+        if is_default_host {
+            env_var::string(DRA_CODEBERG_TOKEN)
+        } else {
+            None
+        }
+    })
+}
+
+pub fn host_token_env_var(prefix: &str, host: &str) -> String {
+    let host = host
+        .chars()
+        .map(|x| {
+            if x.is_ascii_alphanumeric() {
+                x.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{}_{}", prefix, host)
+}
+
+fn github_cli_token(host: &str) -> Option<String> {
+    Command::new("gh")
+        .args(["auth", "token", "--hostname", host_without_port(host)])
+        .output()
+        .ok()
+        .and_then(|output| {
+            if output.status.success() {
+                String::from_utf8(output.stdout).ok()
+            } else {
+                None
+            }
+        })
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+}
+
+fn host_without_port(host: &str) -> &str {
+    host.split(':').next().unwrap_or(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("ghe.example.com", "DRA_GITHUB_TOKEN_GHE_EXAMPLE_COM"; "host")]
+    #[test_case("localhost:8080", "DRA_GITHUB_TOKEN_LOCALHOST_8080"; "host with port")]
+    fn token_env_var_by_host(host: &str, expected: &str) {
+        assert_eq!(expected, host_token_env_var(DRA_GITHUB_TOKEN, host));
+    }
+}
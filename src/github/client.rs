@@ -1,73 +1,235 @@
+use crate::cli::output::{self, Event};
+use crate::cli::progress;
 use crate::env_var;
 use crate::github::constants::{
-    DRA_DISABLE_GITHUB_AUTHENTICATION, DRA_GITHUB_TOKEN, GH_TOKEN, GITHUB_TOKEN,
+    DRA_DISABLE_GITHUB_AUTHENTICATION, DRA_GITHUB_API_URL, DRA_RETRIES, DRA_TIMEOUT,
+    DRA_WAIT_FOR_RATE_LIMIT,
 };
 use crate::github::error::GithubError;
+use crate::github::gitea_provider::GiteaProvider;
+use crate::github::github_provider::GitHubProvider;
+use crate::github::gitlab_provider::GitLabProvider;
+use crate::github::provider::ReleaseProvider;
 use crate::github::release::{Asset, Release, Tag};
-use crate::github::release_response::ReleaseResponse;
-use crate::github::repository::Repository;
+use crate::github::repository::{CODEBERG_HOST, GITHUB_HOST, GITLAB_HOST, Provider, Repository};
+use crate::github::retry::{RetryPolicy, retry_after};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, VecDeque};
 use std::io::Read;
-use std::process::Command;
+use std::sync::Mutex;
 use std::time::Duration;
 
+const DEFAULT_RETRIES: u32 = 3;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const GITHUB_API_URL: &str = "https://api.github.com";
+// NOTE: a server that keeps answering with a rate limit error would otherwise be waited forever
+const MAX_RATE_LIMIT_WAITS: u32 = 3;
+
 pub struct GithubClient {
-    pub token: Option<String>,
+    options: ClientOptions,
+    is_auth_disabled: bool,
+    /// Hosts that can receive authentication tokens, with the provider they belong to
+    /// and whether they are the provider default host
+    hosts: Mutex<HashMap<String, (Provider, bool)>>,
+    /// Authentication tokens by host, resolved the first time a host is used
+    tokens: Mutex<HashMap<String, Option<String>>>,
+}
+
+/// Network options shared by all requests
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientOptions {
+    retry: RetryPolicy,
+    /// Timeout of GitHub API requests. Asset downloads only use it to connect and wait for the response
+    timeout: Duration,
+    /// Sleep until the rate limit resets instead of failing
+    wait_for_rate_limit: bool,
+    /// API used for repositories without a host (`{owner}/{repo}`), e.g. a GitHub Enterprise Server
+    api_url: String,
+}
+
+impl ClientOptions {
+    /// Options not given from cli are read from `DRA_RETRIES`, `DRA_TIMEOUT` (seconds),
+    /// `DRA_WAIT_FOR_RATE_LIMIT` and `DRA_GITHUB_API_URL` environment variables
+    pub fn new(
+        retries: Option<u32>,
+        timeout: Option<u64>,
+        wait_for_rate_limit: bool,
+        api_url: Option<String>,
+    ) -> Self {
+        let retries = retries
+            .or_else(|| env_var::number(DRA_RETRIES))
+            .unwrap_or(DEFAULT_RETRIES);
+        let timeout = timeout
+            .or_else(|| env_var::number(DRA_TIMEOUT))
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TIMEOUT);
+        let api_url = api_url
+            .or_else(|| env_var::string(DRA_GITHUB_API_URL))
+            .map(|x| x.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| GITHUB_API_URL.to_string());
+        Self {
+            retry: RetryPolicy::new(retries),
+            timeout,
+            wait_for_rate_limit: wait_for_rate_limit || env_var::boolean(DRA_WAIT_FOR_RATE_LIMIT),
+            api_url,
+        }
+    }
 }
 
 impl GithubClient {
-    pub fn new(token: Option<String>) -> Self {
-        Self { token }
+    pub fn from_environment(options: &ClientOptions) -> Self {
+        let mut hosts = HashMap::from([
+            (GITHUB_HOST.to_string(), (Provider::GitHub, true)),
+            (GITLAB_HOST.to_string(), (Provider::GitLab, true)),
+            (CODEBERG_HOST.to_string(), (Provider::Gitea, true)),
+        ]);
+        // NOTE: github.com tokens (e.g. GITHUB_TOKEN) must never be sent to another API,
+        // which uses the same tokens of a GitHub Enterprise Server repository URL
+        if let Some(host) = url_host(&options.api_url) {
+            hosts.entry(host).or_insert((Provider::GitHub, false));
+        }
+
+        Self {
+            options: options.clone(),
+            is_auth_disabled: env_var::boolean(DRA_DISABLE_GITHUB_AUTHENTICATION),
+            hosts: Mutex::new(hosts),
+            tokens: Mutex::new(HashMap::new()),
+        }
     }
 
-    pub fn from_environment() -> Self {
-        let is_auth_disabled = env_var::boolean(DRA_DISABLE_GITHUB_AUTHENTICATION);
-        if is_auth_disabled {
-            return Self::new(None);
+    fn release_provider(&self, provider: Provider) -> Box<dyn ReleaseProvider> {
+        match provider {
+            Provider::GitHub => Box::new(GitHubProvider::new(&self.options.api_url)),
+            Provider::GitLab => Box::new(GitLabProvider),
+            Provider::Gitea => Box::new(GiteaProvider),
         }
+    }
 
-        let token = env_var::string(DRA_GITHUB_TOKEN)
-            .or_else(|| env_var::string(GITHUB_TOKEN))
-            .or_else(|| env_var::string(GH_TOKEN))
-            .or_else(github_cli_token);
+    /// Authentication header for the url, only sent to known hosts
+    fn authorization_for(&self, url: &str) -> Option<(&'static str, String)> {
+        if self.is_auth_disabled {
+            return None;
+        }
 
-        Self::new(token)
+        let host = url_host(url)?;
+        let (provider, is_default_host) = *self.hosts.lock().unwrap().get(&host)?;
+        let release_provider = self.release_provider(provider);
+        let token = self
+            .tokens
+            .lock()
+            .unwrap()
+            .entry(host.clone())
+            .or_insert_with(|| release_provider.token(&host, is_default_host))
+            .clone()?;
+        Some(release_provider.authorization(&token))
     }
 
     fn get(
         &self,
         url: &str,
-        timeout: Option<Duration>,
+        timeout: Timeout,
     ) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
-        let agent: ureq::Agent = ureq::Agent::config_builder()
-            .timeout_global(timeout)
-            .build()
-            .into();
-
-        self.token
-            .as_ref()
-            .map(|x| {
-                agent
-                    .get(url)
-                    .header("Authorization", &format!("token {}", x))
-            })
+        let config = ureq::Agent::config_builder().http_status_as_error(false);
+        let config = match timeout {
+            Timeout::Global => config.timeout_global(Some(self.options.timeout)),
+            Timeout::Response => config
+                .timeout_connect(Some(self.options.timeout))
+                .timeout_recv_response(Some(self.options.timeout)),
+        };
+        let agent: ureq::Agent = config.build().into();
+
+        self.authorization_for(url)
+            .map(|(name, value)| agent.get(url).header(name, &value))
             .unwrap_or_else(|| agent.get(url))
     }
 
-    // DOCS:
-    // - https://docs.github.com/en/rest/releases/releases#get-the-latest-release
-    // - https://docs.github.com/en/rest/releases/releases#get-a-release-by-tag-name
+    /// Backoff of failed requests, also used to resume interrupted downloads
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.options.retry
+    }
+
+    /// Send a GET request, retrying transient failures with exponential backoff
+    fn send(
+        &self,
+        request: impl Fn() -> ureq::RequestBuilder<ureq::typestate::WithoutBody>,
+    ) -> Result<ureq::http::Response<ureq::Body>, GithubError> {
+        let mut attempt = 0;
+        let mut rate_limit_waits = 0;
+        loop {
+            let (error, retry_after) = match request().call() {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => (
+                    GithubError::from_response(response.status().as_u16(), response.headers()),
+                    retry_after(response.headers()),
+                ),
+                Err(error) => (GithubError::from(error), None),
+            };
+
+            if let Some(wait) = error
+                .rate_limit_wait()
+                .filter(|_| self.options.wait_for_rate_limit)
+                .filter(|_| rate_limit_waits < MAX_RATE_LIMIT_WAITS)
+            {
+                rate_limit_waits += 1;
+                let wait = wait + Duration::from_secs(1);
+                progress::eprintln(&format!(
+                    "GitHub API rate limit exceeded, waiting {} before continuing...",
+                    humantime::format_duration(Duration::from_secs(wait.as_secs()))
+                ));
+                output::emit(Event::RateLimited {
+                    wait_seconds: wait.as_secs(),
+                });
+                std::thread::sleep(wait);
+                continue;
+            }
+
+            if !self.options.retry.should_retry(attempt, &error) {
+                return Err(error);
+            }
+            attempt += 1;
+            std::thread::sleep(self.options.retry.delay(attempt, retry_after));
+        }
+    }
+
     pub fn get_release(
         &self,
         repository: &Repository,
         tag: Option<&Tag>,
     ) -> Result<Release, GithubError> {
-        let url = get_release_url(repository, tag);
-        let response = self
-            .get(&url, Some(Duration::from_secs(5)))
-            .call()
-            .map_err(GithubError::from)?;
+        self.register_host(repository);
+        self.release_provider(repository.provider)
+            .get_release(self, repository, tag)
+    }
+
+    /// Releases from newest to oldest, each page is fetched only when needed
+    pub fn releases<'a>(&'a self, repository: &'a Repository) -> Releases<'a> {
+        self.register_host(repository);
+        Releases {
+            client: self,
+            provider: self.release_provider(repository.provider),
+            repository,
+            page: 1,
+            buffer: VecDeque::new(),
+            is_last_page: false,
+        }
+    }
+
+    /// Self-hosted instances of the repository can receive authentication tokens
+    fn register_host(&self, repository: &Repository) {
+        if let Some(host) = &repository.host {
+            self.hosts
+                .lock()
+                .unwrap()
+                .entry(host.clone())
+                .or_insert((repository.provider, false));
+        }
+    }
+
+    /// Send a GET request to an API and deserialize the JSON response
+    pub(super) fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, GithubError> {
+        let response = self.send(|| self.get(url, Timeout::Global))?;
         let (_, mut body) = response.into_parts();
-        deserialize(&mut body).map(to_release(repository))
+        body.read_json::<T>().map_err(GithubError::from)
     }
 
     // DOCS: https://docs.github.com/en/rest/releases/assets#get-a-release-asset
@@ -75,53 +237,145 @@ impl GithubClient {
         &self,
         asset: &Asset,
     ) -> Result<(impl Read + Send, Option<u64>), GithubError> {
-        let response = self
-            .get(&asset.download_url, None)
-            .header("Accept", "application/vnd.github.raw")
-            .call()
-            .map_err(GithubError::from)?;
+        self.download_asset_stream_from(asset, 0, None)
+            .map(|x| (x.reader, x.content_length))
+    }
+
+    /// Request the asset starting from `offset` bytes, using an HTTP Range request.
+    /// The range is only sent with the `validator` of the bytes already downloaded (`If-Range`),
+    /// so the server sends the whole asset when it has changed since then (see [AssetStream::offset])
+    pub fn download_asset_stream_from(
+        &self,
+        asset: &Asset,
+        offset: u64,
+        validator: Option<&str>,
+    ) -> Result<AssetStream<impl Read + Send + use<>>, GithubError> {
+        let request = || {
+            self.get(&asset.download_url, Timeout::Response)
+                .header("Accept", "application/vnd.github.raw")
+        };
+        let response = match validator.filter(|_| offset > 0) {
+            Some(validator) => match self.send(|| {
+                request()
+                    .header("Range", &format!("bytes={}-", offset))
+                    .header("If-Range", validator)
+            }) {
+                // NOTE: the range is not valid anymore (e.g. the asset has changed), start over
+                Err(GithubError::Http(e)) if matches!(e.as_ref(), ureq::Error::StatusCode(416)) => {
+                    return self.download_asset_stream_from(asset, 0, None);
+                }
+                other => other,
+            },
+            None => self.send(request),
+        }?;
+
+        let is_partial_content = response.status() == 206;
         let (head, body) = response.into_parts();
-        let content_length = head
-            .headers
-            .get("Content-Length")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse::<u64>().ok());
-        Ok((body.into_reader(), content_length))
+        let header = |name: &str| {
+            head.headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let content_length = header("Content-Length").and_then(|v| v.parse::<u64>().ok());
+        // NOTE: If-Range only accepts strong ETags
+        let validator = header("ETag")
+            .filter(|x| !x.starts_with("W/"))
+            .or_else(|| header("Last-Modified"));
+        let offset = if is_partial_content { offset } else { 0 };
+        Ok(AssetStream {
+            reader: body.into_reader(),
+            content_length: content_length.map(|x| x + offset),
+            offset,
+            validator,
+        })
     }
 }
 
-fn github_cli_token() -> Option<String> {
-    Command::new("gh")
-        .args(["auth", "token"])
-        .output()
-        .ok()
-        .and_then(|output| {
-            if output.status.success() {
-                String::from_utf8(output.stdout).ok()
-            } else {
-                None
+const RELEASES_PER_PAGE: u32 = 50;
+
+pub struct Releases<'a> {
+    client: &'a GithubClient,
+    provider: Box<dyn ReleaseProvider>,
+    repository: &'a Repository,
+    page: u32,
+    buffer: VecDeque<Release>,
+    is_last_page: bool,
+}
+
+impl Iterator for Releases<'_> {
+    type Item = Result<Release, GithubError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.is_last_page {
+            let releases = self.provider.list_releases(
+                self.client,
+                self.repository,
+                self.page,
+                RELEASES_PER_PAGE,
+            );
+            match releases {
+                Ok(releases) => {
+                    self.is_last_page = releases.len() < RELEASES_PER_PAGE as usize;
+                    self.page += 1;
+                    self.buffer.extend(releases);
+                }
+                Err(e) => {
+                    self.is_last_page = true;
+                    return Some(Err(e));
+                }
             }
-        })
-        .map(|x| x.trim().to_string())
+        }
+        self.buffer.pop_front().map(Ok)
+    }
 }
 
-fn get_release_url(repository: &Repository, tag: Option<&Tag>) -> String {
-    format!(
-        "https://api.github.com/repos/{owner}/{repo}/releases/{release}",
-        owner = &repository.owner,
-        repo = &repository.repo,
-        release = tag
-            .map(|t| format!("tags/{}", t.0))
-            .unwrap_or_else(|| String::from("latest"))
-    )
+enum Timeout {
+    /// Whole request, including reading the response body
+    Global,
+    /// Connect and receive the response, so large bodies can take as long as needed
+    Response,
 }
 
-fn deserialize(response: &mut ureq::Body) -> Result<ReleaseResponse, GithubError> {
-    response
-        .read_json::<ReleaseResponse>()
-        .map_err(GithubError::from)
+pub struct AssetStream<R: Read + Send> {
+    pub reader: R,
+    /// Total size of the asset, including the bytes skipped with `offset`
+    pub content_length: Option<u64>,
+    /// Number of bytes skipped at the beginning of the asset.
+    /// It's 0 when the whole asset is downloaded
+    pub offset: u64,
+    /// ETag or Last-Modified of the asset, used to resume the download only if it doesn't change
+    pub validator: Option<String>,
 }
 
-fn to_release(repository: &Repository) -> impl Fn(ReleaseResponse) -> Release + '_ {
-    |response| Release::from_response(response, repository)
+/// Both `github.com` and `api.github.com` use the same authentication
+pub(super) fn url_host(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    let host = host
+        .strip_prefix("api.")
+        .filter(|x| *x == GITHUB_HOST)
+        .unwrap_or(host);
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("https://api.github.com", "github.com", true; "github")]
+    #[test_case("https://ghe.example.com/api/v3", "ghe.example.com", false; "enterprise")]
+    #[test_case("http://localhost:8080", "localhost:8080", false; "localhost")]
+    fn api_url_host(api_url: &str, host: &str, is_default_host: bool) {
+        let options = ClientOptions::new(None, None, false, Some(api_url.to_string()));
+
+        let client = GithubClient::from_environment(&options);
+
+        let hosts = client.hosts.lock().unwrap();
+        assert_eq!(Some(&(Provider::GitHub, is_default_host)), hosts.get(host));
+    }
 }
@@ -2,3 +2,13 @@ pub const DRA_GITHUB_TOKEN: &str = "DRA_GITHUB_TOKEN";
 pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
 pub const GH_TOKEN: &str = "GH_TOKEN";
 pub const DRA_DISABLE_GITHUB_AUTHENTICATION: &str = "DRA_DISABLE_GITHUB_AUTHENTICATION";
+pub const DRA_RETRIES: &str = "DRA_RETRIES";
+pub const DRA_TIMEOUT: &str = "DRA_TIMEOUT";
+pub const DRA_WAIT_FOR_RATE_LIMIT: &str = "DRA_WAIT_FOR_RATE_LIMIT";
+pub const DRA_GITHUB_API_URL: &str = "DRA_GITHUB_API_URL";
+pub const GH_ENTERPRISE_TOKEN: &str = "GH_ENTERPRISE_TOKEN";
+pub const GITHUB_ENTERPRISE_TOKEN: &str = "GITHUB_ENTERPRISE_TOKEN";
+pub const DRA_GITLAB_TOKEN: &str = "DRA_GITLAB_TOKEN";
+pub const GITLAB_TOKEN: &str = "GITLAB_TOKEN";
+pub const DRA_GITEA_TOKEN: &str = "DRA_GITEA_TOKEN";
+pub const DRA_CODEBERG_TOKEN: &str = "DRA_CODEBERG_TOKEN";
@@ -1,12 +1,21 @@
 use crate::github::constants::{DRA_GITHUB_TOKEN, GH_TOKEN, GITHUB_TOKEN};
+use crate::github::retry::retry_after;
 use std::fmt::Formatter;
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug)]
 pub enum GithubError {
     Http(Box<ureq::Error>),
     JsonDeserialization(String),
     RepositoryOrReleaseNotFound,
-    RateLimitExceeded,
+    /// Primary rate limit, `reset` is when new requests are allowed again
+    RateLimitExceeded {
+        reset: Option<SystemTime>,
+    },
+    SecondaryRateLimitExceeded {
+        retry_after: Option<Duration>,
+    },
+    Forbidden,
     Unauthorized,
 }
 
@@ -14,12 +23,70 @@ impl GithubError {
     pub fn from(error: ureq::Error) -> Self {
         match error {
             ureq::Error::StatusCode(401) => Self::Unauthorized,
-            ureq::Error::StatusCode(403) => Self::RateLimitExceeded,
+            ureq::Error::StatusCode(403) => Self::Forbidden,
             ureq::Error::StatusCode(404) => Self::RepositoryOrReleaseNotFound,
             ureq::Error::Json(error) => Self::JsonDeserialization(error.to_string()),
             other => Self::Http(Box::new(other)),
         }
     }
+
+    // DOCS: https://docs.github.com/en/rest/using-the-rest-api/troubleshooting-the-rest-api#rate-limit-errors
+    pub fn from_response(status: u16, headers: &ureq::http::HeaderMap) -> Self {
+        if status != 403 && status != 429 {
+            return Self::from(ureq::Error::StatusCode(status));
+        }
+
+        let remaining = header_number(headers, "X-RateLimit-Remaining");
+        let retry_after = retry_after(headers);
+        match (remaining, retry_after) {
+            (Some(0), None) => Self::RateLimitExceeded {
+                reset: header_number(headers, "X-RateLimit-Reset")
+                    .map(|x| SystemTime::UNIX_EPOCH + Duration::from_secs(x)),
+            },
+            (_, Some(_)) => Self::SecondaryRateLimitExceeded { retry_after },
+            (_, None) if status == 429 => Self::SecondaryRateLimitExceeded { retry_after },
+            (_, None) => Self::Forbidden,
+        }
+    }
+
+    /// Time to wait before the request can succeed again, when the error is caused by a rate limit.
+    /// It's never longer than [MAX_RATE_LIMIT_WAIT], whatever the server sends
+    pub fn rate_limit_wait(&self) -> Option<Duration> {
+        let wait = match self {
+            GithubError::RateLimitExceeded { reset: Some(reset) } => {
+                reset.duration_since(SystemTime::now()).unwrap_or_default()
+            }
+            GithubError::SecondaryRateLimitExceeded { retry_after } => {
+                retry_after.unwrap_or(SECONDARY_RATE_LIMIT_WAIT)
+            }
+            _ => return None,
+        };
+        Some(wait.min(MAX_RATE_LIMIT_WAIT))
+    }
+}
+
+// NOTE: GitHub recommends to wait at least one minute when there's no Retry-After header
+const SECONDARY_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+// NOTE: the primary rate limit resets every hour, longer waits come from unexpected headers
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60 * 60);
+
+fn header_number(headers: &ureq::http::HeaderMap, name: &str) -> Option<u64> {
+    headers
+        .get(name)
+        .and_then(|x| x.to_str().ok())
+        .and_then(|x| x.trim().parse::<u64>().ok())
+}
+
+fn format_reset(reset: &SystemTime) -> String {
+    let remaining = reset
+        .duration_since(SystemTime::now())
+        .map(|x| Duration::from_secs(x.as_secs()))
+        .unwrap_or_default();
+    format!(
+        "{} (in {})",
+        humantime::format_rfc3339_seconds(*reset),
+        humantime::format_duration(remaining)
+    )
 }
 
 fn authentication_tokens() -> String {
@@ -36,17 +103,37 @@ impl std::fmt::Display for GithubError {
             GithubError::RepositoryOrReleaseNotFound => {
                 f.write_str("Repository or release not found")
             }
-            GithubError::RateLimitExceeded => {
+            GithubError::RateLimitExceeded { reset } => {
+                let reset = reset
+                    .as_ref()
+                    .map(|x| format!("\nThe rate limit resets at {}.", format_reset(x)))
+                    .unwrap_or_default();
                 let message = format!(
-                    "GitHub API rate limit exceeded.
+                    "GitHub API rate limit exceeded.{}
 Export one of {} environment variable to avoid this error.
 Otherwise, login with GitHub cli 'gh auth login' to use the authentication token automatically.
+Use --wait-for-rate-limit to wait until the rate limit resets.
 
 More information can be found at https://github.com/devmatteini/dra#usage",
+                    reset,
                     authentication_tokens()
                 );
                 f.write_str(&message)
             }
+            GithubError::SecondaryRateLimitExceeded { retry_after } => {
+                let retry_after = retry_after
+                    .map(|x| format!(" Retry after {}.", humantime::format_duration(x)))
+                    .unwrap_or_default();
+                let message = format!(
+                    "GitHub API secondary rate limit exceeded, too many requests in a short time.{}
+Use --wait-for-rate-limit to wait and continue automatically.",
+                    retry_after
+                );
+                f.write_str(&message)
+            }
+            GithubError::Forbidden => f.write_str(
+                "Access forbidden. Make sure your GitHub token has permission to access this repository",
+            ),
             GithubError::Unauthorized => {
                 let message = format!(
                     "Invalid GitHub credentials.
@@ -59,3 +146,100 @@ Otherwise, login with GitHub cli 'gh auth login' to use the authentication token
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn primary_rate_limit() {
+        let headers = headers(&[
+            ("X-RateLimit-Remaining", "0"),
+            ("X-RateLimit-Reset", "1800000000"),
+        ]);
+
+        let result = GithubError::from_response(403, &headers);
+
+        assert!(
+            matches!(result, GithubError::RateLimitExceeded { reset: Some(x) } if x == SystemTime::UNIX_EPOCH + Duration::from_secs(1800000000)),
+            "Unexpected {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn secondary_rate_limit() {
+        let headers = headers(&[("X-RateLimit-Remaining", "42"), ("Retry-After", "30")]);
+
+        let result = GithubError::from_response(403, &headers);
+
+        assert!(
+            matches!(result, GithubError::SecondaryRateLimitExceeded { retry_after: Some(x) } if x == Duration::from_secs(30)),
+            "Unexpected {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn too_many_requests() {
+        let result = GithubError::from_response(429, &headers(&[]));
+
+        assert!(
+            matches!(
+                result,
+                GithubError::SecondaryRateLimitExceeded { retry_after: None }
+            ),
+            "Unexpected {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn forbidden() {
+        let headers = headers(&[("X-RateLimit-Remaining", "42")]);
+
+        let result = GithubError::from_response(403, &headers);
+
+        assert!(
+            matches!(result, GithubError::Forbidden),
+            "Unexpected {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn wait_until_primary_rate_limit_resets() {
+        let reset = SystemTime::now() + Duration::from_secs(120);
+        let error = GithubError::RateLimitExceeded { reset: Some(reset) };
+
+        let result = error.rate_limit_wait().unwrap();
+
+        assert!(result > Duration::from_secs(100) && result <= Duration::from_secs(120));
+    }
+
+    #[test_case(GithubError::RateLimitExceeded { reset: Some(SystemTime::now() + Duration::from_secs(6 * 60 * 60)) }; "primary rate limit")]
+    #[test_case(GithubError::SecondaryRateLimitExceeded { retry_after: Some(Duration::from_secs(6 * 60 * 60)) }; "secondary rate limit")]
+    fn wait_at_most_one_hour(error: GithubError) {
+        let result = error.rate_limit_wait();
+
+        assert_eq!(Some(MAX_RATE_LIMIT_WAIT), result);
+    }
+
+    #[test]
+    fn no_wait_for_other_errors() {
+        assert_eq!(None, GithubError::Forbidden.rate_limit_wait());
+        assert_eq!(
+            None,
+            GithubError::RateLimitExceeded { reset: None }.rate_limit_wait()
+        );
+    }
+
+    fn headers(values: &[(&'static str, &str)]) -> ureq::http::HeaderMap {
+        let mut headers = ureq::http::HeaderMap::new();
+        for (name, value) in values {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+}
@@ -0,0 +1,130 @@
+use crate::github::auth;
+use crate::github::client::GithubClient;
+use crate::github::error::GithubError;
+use crate::github::provider::ReleaseProvider;
+use crate::github::release::{Release, Tag};
+use crate::github::release_response::ReleaseResponse;
+use crate::github::repository::Repository;
+
+/// Gitea, Forgejo and Codeberg share the same releases API, which is compatible with GitHub responses
+pub struct GiteaProvider;
+
+impl ReleaseProvider for GiteaProvider {
+    // DOCS: https://docs.gitea.com/api/#tag/repository/operation/repoListReleases
+    fn get_release(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        tag: Option<&Tag>,
+    ) -> Result<Release, GithubError> {
+        let url = get_release_url(repository, tag);
+        client
+            .get_json::<ReleaseResponse>(&url)
+            .map(|response| Release::from_response(response, repository))
+    }
+
+    fn list_releases(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<Release>, GithubError> {
+        let url = format!(
+            "{}/api/v1/repos/{}/{}/releases?limit={}&page={}",
+            repository.base_url(),
+            repository.owner,
+            repository.repo,
+            per_page,
+            page
+        );
+        client
+            .get_json::<Vec<ReleaseResponse>>(&url)
+            .map(|releases| {
+                releases
+                    .into_iter()
+                    .map(|x| Release::from_response(x, repository))
+                    .collect()
+            })
+    }
+
+    fn token(&self, host: &str, is_default_host: bool) -> Option<String> {
+        auth::gitea_token(host, is_default_host)
+    }
+
+    fn authorization(&self, token: &str) -> (&'static str, String) {
+        ("Authorization", format!("token {}", token))
+    }
+}
+
+fn get_release_url(repository: &Repository, tag: Option<&Tag>) -> String {
+    format!(
+        "{base_url}/api/v1/repos/{owner}/{repo}/releases/{release}",
+        base_url = repository.base_url(),
+        owner = &repository.owner,
+        repo = &repository.repo,
+        release = tag
+            .map(|t| format!("tags/{}", encode(&t.0)))
+            .unwrap_or_else(|| String::from("latest"))
+    )
+}
+
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("codeberg:foo/bar", None, "https://codeberg.org/api/v1/repos/foo/bar/releases/latest"; "codeberg latest")]
+    #[test_case("forgejo:https://git.example.com/foo/bar", Some("v1.0.0"), "https://git.example.com/api/v1/repos/foo/bar/releases/tags/v1.0.0"; "self-hosted tag")]
+    #[test_case("codeberg:foo/bar", Some("release/1.0"), "https://codeberg.org/api/v1/repos/foo/bar/releases/tags/release%2F1.0"; "encoded tag")]
+    fn release_url(repository: &str, tag: Option<&str>, expected: &str) {
+        let repository = Repository::try_parse(repository).unwrap();
+        let tag = tag.map(|x| Tag(x.to_string()));
+
+        let result = get_release_url(&repository, tag.as_ref());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn release_from_response() {
+        let response: ReleaseResponse = serde_json::from_str(
+            r#"{
+              "id": 1,
+              "tag_name": "v1.0.0",
+              "tarball_url": "https://codeberg.org/foo/bar/archive/v1.0.0.tar.gz",
+              "zipball_url": "https://codeberg.org/foo/bar/archive/v1.0.0.zip",
+              "assets": [
+                {
+                  "id": 2,
+                  "name": "bar-linux-amd64.tar.gz",
+                  "size": 1024,
+                  "browser_download_url": "https://codeberg.org/foo/bar/releases/download/v1.0.0/bar-linux-amd64.tar.gz"
+                }
+              ]
+            }"#,
+        )
+        .unwrap();
+        let repository = Repository::try_parse("codeberg:foo/bar").unwrap();
+
+        let result = Release::from_response(response, &repository);
+
+        let names = result
+            .assets
+            .iter()
+            .map(|x| x.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "bar-linux-amd64.tar.gz",
+                "bar-1.0.0-source-code.tar.gz",
+                "bar-1.0.0-source-code.zip"
+            ],
+            names
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::github::auth;
+use crate::github::client::{GithubClient, url_host};
+use crate::github::error::GithubError;
+use crate::github::provider::ReleaseProvider;
+use crate::github::release::{Release, Tag};
+use crate::github::release_response::ReleaseResponse;
+use crate::github::repository::Repository;
+
+pub struct GitHubProvider {
+    /// API used for repositories without a host
+    api_url: String,
+}
+
+impl GitHubProvider {
+    pub fn new(api_url: &str) -> Self {
+        Self {
+            api_url: api_url.to_string(),
+        }
+    }
+
+    /// GitHub Enterprise Server exposes the REST API at `{scheme}://{host}/api/v3`.
+    /// The configured API is used when it belongs to the repository host, so its scheme is kept
+    fn api_url_for(&self, repository: &Repository) -> String {
+        match &repository.host {
+            Some(host) if url_host(&self.api_url).as_ref() != Some(host) => {
+                format!("{}/api/v3", repository.base_url())
+            }
+            _ => self.api_url.clone(),
+        }
+    }
+}
+
+impl ReleaseProvider for GitHubProvider {
+    // DOCS:
+    // - https://docs.github.com/en/rest/releases/releases#get-the-latest-release
+    // - https://docs.github.com/en/rest/releases/releases#get-a-release-by-tag-name
+    fn get_release(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        tag: Option<&Tag>,
+    ) -> Result<Release, GithubError> {
+        let url = get_release_url(&self.api_url_for(repository), repository, tag);
+        client
+            .get_json::<ReleaseResponse>(&url)
+            .map(|response| Release::from_response(response, repository))
+    }
+
+    // DOCS: https://docs.github.com/en/rest/releases/releases#list-releases
+    fn list_releases(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<Release>, GithubError> {
+        let url = format!(
+            "{}/repos/{}/{}/releases?per_page={}&page={}",
+            self.api_url_for(repository),
+            repository.owner,
+            repository.repo,
+            per_page,
+            page
+        );
+        client
+            .get_json::<Vec<ReleaseResponse>>(&url)
+            .map(|releases| {
+                releases
+                    .into_iter()
+                    .map(|x| Release::from_response(x, repository))
+                    .collect()
+            })
+    }
+
+    fn token(&self, host: &str, is_default_host: bool) -> Option<String> {
+        auth::github_token(host, is_default_host)
+    }
+
+    fn authorization(&self, token: &str) -> (&'static str, String) {
+        ("Authorization", format!("token {}", token))
+    }
+}
+
+fn get_release_url(api_url: &str, repository: &Repository, tag: Option<&Tag>) -> String {
+    format!(
+        "{api_url}/repos/{owner}/{repo}/releases/{release}",
+        api_url = api_url,
+        owner = &repository.owner,
+        repo = &repository.repo,
+        release = tag
+            .map(|t| format!("tags/{}", t.0))
+            .unwrap_or_else(|| String::from("latest"))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const API_URL: &str = "https://api.github.com";
+
+    #[test_case("foo/bar", API_URL, "https://api.github.com"; "github")]
+    #[test_case("https://ghe.example.com/foo/bar", API_URL, "https://ghe.example.com/api/v3"; "enterprise")]
+    #[test_case("http://ghe.example.com/foo/bar", API_URL, "http://ghe.example.com/api/v3"; "enterprise over http")]
+    #[test_case("https://ghe.example.com/foo/bar", "http://ghe.example.com/api/v3", "http://ghe.example.com/api/v3"; "configured api url")]
+    fn api_url(repository: &str, api_url: &str, expected: &str) {
+        let provider = GitHubProvider::new(api_url);
+        let repository = Repository::try_parse(repository).unwrap();
+
+        assert_eq!(expected, provider.api_url_for(&repository));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::github::auth;
+use crate::github::client::GithubClient;
+use crate::github::error::GithubError;
+use crate::github::provider::ReleaseProvider;
+use crate::github::release::{Asset, Release, ReleaseDetails, Tag};
+use crate::github::repository::Repository;
+use serde::Deserialize;
+
+pub struct GitLabProvider;
+
+#[derive(Deserialize, Debug)]
+struct ReleaseResponse {
+    tag_name: String,
+    name: Option<String>,
+    released_at: Option<String>,
+    /// Release with a future release date
+    #[serde(default)]
+    upcoming_release: bool,
+    assets: AssetsResponse,
+}
+
+#[derive(Deserialize, Debug)]
+struct AssetsResponse {
+    #[serde(default)]
+    sources: Vec<SourceResponse>,
+    #[serde(default)]
+    links: Vec<LinkResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SourceResponse {
+    format: String,
+    url: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct LinkResponse {
+    name: String,
+    url: String,
+    direct_asset_url: Option<String>,
+}
+
+impl ReleaseProvider for GitLabProvider {
+    // DOCS:
+    // - https://docs.gitlab.com/api/releases/#get-the-latest-release
+    // - https://docs.gitlab.com/api/releases/#get-a-release-by-a-tag-name
+    fn get_release(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        tag: Option<&Tag>,
+    ) -> Result<Release, GithubError> {
+        let url = get_release_url(repository, tag);
+        client
+            .get_json::<ReleaseResponse>(&url)
+            .map(|response| to_release(response, repository))
+    }
+
+    // DOCS: https://docs.gitlab.com/api/releases/#list-releases
+    fn list_releases(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<Release>, GithubError> {
+        let url = format!(
+            "{}?per_page={}&page={}",
+            releases_url(repository),
+            per_page,
+            page
+        );
+        client
+            .get_json::<Vec<ReleaseResponse>>(&url)
+            .map(|releases| {
+                releases
+                    .into_iter()
+                    .map(|x| to_release(x, repository))
+                    .collect()
+            })
+    }
+
+    fn token(&self, host: &str, is_default_host: bool) -> Option<String> {
+        auth::gitlab_token(host, is_default_host)
+    }
+
+    fn authorization(&self, token: &str) -> (&'static str, String) {
+        ("PRIVATE-TOKEN", token.to_string())
+    }
+}
+
+fn releases_url(repository: &Repository) -> String {
+    format!(
+        "{base_url}/api/v4/projects/{project}/releases",
+        base_url = repository.base_url(),
+        project = encode(&format!("{}/{}", repository.owner, repository.repo)),
+    )
+}
+
+fn get_release_url(repository: &Repository, tag: Option<&Tag>) -> String {
+    format!(
+        "{releases_url}/{release}",
+        releases_url = releases_url(repository),
+        release = tag
+            .map(|t| encode(&t.0))
+            .unwrap_or_else(|| String::from("permalink/latest"))
+    )
+}
+
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Release links are the equivalent of GitHub assets
+fn to_release(response: ReleaseResponse, repository: &Repository) -> Release {
+    let source = |format: &str| {
+        response
+            .assets
+            .sources
+            .iter()
+            .find(|x| x.format == format)
+            .map(|x| x.url.clone())
+    };
+    let tarball_url = source("tar.gz");
+    let zipball_url = source("zip");
+
+    let details = ReleaseDetails {
+        name: response.name.filter(|x| !x.is_empty()),
+        published_at: response.released_at,
+        prerelease: response.upcoming_release,
+        draft: false,
+    };
+    let assets = response.assets.links.into_iter().map(to_asset).collect();
+    Release::new(
+        repository,
+        Tag(response.tag_name),
+        assets,
+        tarball_url,
+        zipball_url,
+    )
+    .with_details(details)
+}
+
+/// The link name is a free text, so the file name of the url is used as asset name (e.g. `tool.tar.gz`)
+fn to_asset(link: LinkResponse) -> Asset {
+    let download_url = link.direct_asset_url.unwrap_or(link.url);
+    let file_name = download_url
+        .split(['?', '#'])
+        .next()
+        .and_then(|x| x.rsplit('/').next())
+        .filter(|x| !x.is_empty())
+        .map(|x| x.to_string());
+
+    let (name, display_name) = match file_name {
+        Some(file_name) if file_name != link.name => (file_name, Some(link.name)),
+        _ => (link.name, None),
+    };
+    Asset {
+        name,
+        display_name,
+        download_url,
+        digest: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("gitlab:foo/bar", None, "https://gitlab.com/api/v4/projects/foo%2Fbar/releases/permalink/latest"; "latest")]
+    #[test_case("gitlab:group/sub/bar", Some("v1.0.0"), "https://gitlab.com/api/v4/projects/group%2Fsub%2Fbar/releases/v1.0.0"; "nested group and tag")]
+    #[test_case("gitlab:https://git.example.com/foo/bar", Some("release/1.0"), "https://git.example.com/api/v4/projects/foo%2Fbar/releases/release%2F1.0"; "self-hosted")]
+    fn release_url(repository: &str, tag: Option<&str>, expected: &str) {
+        let repository = Repository::try_parse(repository).unwrap();
+        let tag = tag.map(|x| Tag(x.to_string()));
+
+        let result = get_release_url(&repository, tag.as_ref());
+
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn release_from_response() {
+        let response: ReleaseResponse = serde_json::from_str(
+            r#"{
+              "tag_name": "v1.0.0",
+              "assets": {
+                "count": 4,
+                "sources": [
+                  {"format": "zip", "url": "https://gitlab.com/foo/bar/-/archive/v1.0.0/bar-v1.0.0.zip"},
+                  {"format": "tar.gz", "url": "https://gitlab.com/foo/bar/-/archive/v1.0.0/bar-v1.0.0.tar.gz"},
+                  {"format": "tar.bz2", "url": "https://gitlab.com/foo/bar/-/archive/v1.0.0/bar-v1.0.0.tar.bz2"}
+                ],
+                "links": [
+                  {
+                    "name": "Linux binary",
+                    "url": "https://gitlab.com/foo/bar/-/package_files/1/download",
+                    "direct_asset_url": "https://gitlab.com/foo/bar/-/releases/v1.0.0/downloads/bar-linux-amd64.tar.gz"
+                  },
+                  {
+                    "name": "bar-checksums.txt",
+                    "url": "https://example.com/bar-checksums.txt"
+                  }
+                ]
+              }
+            }"#,
+        )
+        .unwrap();
+        let repository = Repository::try_parse("gitlab:foo/bar").unwrap();
+
+        let result = to_release(response, &repository);
+
+        assert_eq!("v1.0.0", result.tag.0);
+        let names = result
+            .assets
+            .iter()
+            .map(|x| (x.name.as_str(), x.show_name(), x.download_url.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (
+                    "bar-linux-amd64.tar.gz",
+                    "Linux binary",
+                    "https://gitlab.com/foo/bar/-/releases/v1.0.0/downloads/bar-linux-amd64.tar.gz"
+                ),
+                (
+                    "bar-checksums.txt",
+                    "bar-checksums.txt",
+                    "https://example.com/bar-checksums.txt"
+                ),
+                (
+                    "bar-1.0.0-source-code.tar.gz",
+                    "Source code (tar.gz)",
+                    "https://gitlab.com/foo/bar/-/archive/v1.0.0/bar-v1.0.0.tar.gz"
+                ),
+                (
+                    "bar-1.0.0-source-code.zip",
+                    "Source code (zip)",
+                    "https://gitlab.com/foo/bar/-/archive/v1.0.0/bar-v1.0.0.zip"
+                ),
+            ],
+            names
+        );
+    }
+}
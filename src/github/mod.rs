@@ -1,7 +1,13 @@
+mod auth;
 pub mod client;
 mod constants;
 pub mod error;
+mod gitea_provider;
+mod github_provider;
+mod gitlab_provider;
+mod provider;
 pub mod release;
 mod release_response;
 pub mod repository;
+mod retry;
 pub mod tagged_asset;
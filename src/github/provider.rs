@@ -0,0 +1,33 @@
+use crate::github::client::GithubClient;
+use crate::github::error::GithubError;
+use crate::github::release::{Release, Tag};
+use crate::github::repository::Repository;
+
+/// Service hosting releases (e.g. GitHub, GitLab).
+/// Assets are downloaded from their url by [GithubClient], so only fetching releases
+/// and authentication differ between providers
+pub trait ReleaseProvider {
+    /// Fetch the release with the given tag, or the latest one
+    fn get_release(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        tag: Option<&Tag>,
+    ) -> Result<Release, GithubError>;
+
+    /// Releases sorted from newest to oldest, `page` starts from 1
+    fn list_releases(
+        &self,
+        client: &GithubClient,
+        repository: &Repository,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<Release>, GithubError>;
+
+    /// Authentication token for the given host.
+    /// Tokens of the default host (e.g. github.com) must never be used for other hosts
+    fn token(&self, host: &str, is_default_host: bool) -> Option<String>;
+
+    /// HTTP header (name and value) used to authenticate requests
+    fn authorization(&self, token: &str) -> (&'static str, String);
+}
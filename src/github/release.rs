@@ -1,26 +1,71 @@
+use crate::checksum::Checksum;
 use crate::github::release_response::{AssetResponse, ReleaseResponse};
 use crate::github::repository::Repository;
 
-#[derive(Debug)]
+const SOURCE_CODE_TAR_GZ: &str = "Source code (tar.gz)";
+const SOURCE_CODE_ZIP: &str = "Source code (zip)";
+
+#[derive(Debug, Clone)]
 pub struct Tag(pub String);
 
 impl Tag {
+    /// Tag with every `v` removed (e.g. `1.4.2` for `v1.4.2`), the value of the `{tag}` placeholder
     pub fn version(&self) -> String {
         self.0.replace('v', "")
     }
+
+    /// Tag without its prefix (e.g. `1.4.2` for `v1.4.2` or `nvim-v1.4.2`).
+    /// Tags without a version are returned as they are
+    pub fn without_prefix(&self) -> String {
+        let chars = self.0.char_indices().collect::<Vec<_>>();
+        let is_boundary = |i: usize| i == 0 || !chars[i - 1].1.is_alphanumeric();
+        chars
+            .iter()
+            .enumerate()
+            .find(|(i, (_, c))| {
+                c.is_ascii_digit()
+                    && (is_boundary(*i)
+                        || (matches!(chars[i - 1].1, 'v' | 'V') && is_boundary(i - 1)))
+            })
+            .map(|(_, (start, _))| self.0[*start..].to_string())
+            .unwrap_or_else(|| self.0.clone())
+    }
+
+    /// Numeric components of the version (e.g. `[1, 4, 2]` for `v1.4.2-rc.1`)
+    pub fn version_components(&self) -> Vec<String> {
+        self.without_prefix()
+            .split(|c: char| !c.is_ascii_digit() && c != '.')
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .take_while(|x| !x.is_empty())
+            .map(String::from)
+            .collect()
+    }
 }
 
 #[derive(Debug)]
 pub struct Release {
     pub tag: Tag,
     pub assets: Vec<Asset>,
+    pub details: ReleaseDetails,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReleaseDetails {
+    pub name: Option<String>,
+    /// RFC 3339 timestamp, not available for draft releases
+    pub published_at: Option<String>,
+    pub prerelease: bool,
+    pub draft: bool,
+}
+
+#[derive(Debug, Clone)]
 pub struct Asset {
     pub name: String,
     pub display_name: Option<String>,
     pub download_url: String,
+    pub digest: Option<Checksum>,
 }
 
 impl From<AssetResponse> for Asset {
@@ -29,26 +74,53 @@ impl From<AssetResponse> for Asset {
             name: asset.name,
             download_url: asset.browser_download_url,
             display_name: None,
+            digest: asset.digest.and_then(|x| Checksum::try_parse(&x).ok()),
         }
     }
 }
 
 impl Release {
     pub fn from_response(release: ReleaseResponse, repository: &Repository) -> Self {
-        let tag = Tag(release.tag_name);
+        let assets = release.assets.into_iter().map(Asset::from).collect();
+        let details = ReleaseDetails {
+            name: release.name.filter(|x| !x.is_empty()),
+            published_at: release.published_at,
+            prerelease: release.prerelease,
+            draft: release.draft,
+        };
+        Self::new(
+            repository,
+            Tag(release.tag_name),
+            assets,
+            Some(release.tarball_url),
+            Some(release.zipball_url),
+        )
+        .with_details(details)
+    }
 
+    /// Source code archives are added after the release assets
+    pub fn new(
+        repository: &Repository,
+        tag: Tag,
+        assets: Vec<Asset>,
+        tarball_url: Option<String>,
+        zipball_url: Option<String>,
+    ) -> Self {
         let source_code_base = source_code(repository, &tag);
-        let tarball = tarball_asset(release.tarball_url, &source_code_base);
-        let zipball = zipball_asset(release.zipball_url, &source_code_base);
+        let tarball = tarball_url.map(|x| tarball_asset(x, &source_code_base));
+        let zipball = zipball_url.map(|x| zipball_asset(x, &source_code_base));
 
-        let assets = release
-            .assets
-            .into_iter()
-            .map(Asset::from)
-            .chain([tarball, zipball])
-            .collect();
+        let assets = assets.into_iter().chain(tarball).chain(zipball).collect();
+        Self {
+            tag,
+            assets,
+            details: ReleaseDetails::default(),
+        }
+    }
 
-        Self { tag, assets }
+    pub fn with_details(mut self, details: ReleaseDetails) -> Self {
+        self.details = details;
+        self
     }
 }
 
@@ -57,6 +129,13 @@ impl Asset {
         self.display_name.as_ref().unwrap_or(&self.name)
     }
 
+    pub fn is_source_code(&self) -> bool {
+        matches!(
+            self.display_name.as_deref(),
+            Some(SOURCE_CODE_TAR_GZ) | Some(SOURCE_CODE_ZIP)
+        )
+    }
+
     pub fn is_same_name(&self, name: &str) -> bool {
         self.display_name
             .as_deref()
@@ -70,7 +149,8 @@ fn tarball_asset(url: String, base_name: &str) -> Asset {
     Asset {
         name: format!("{}.tar.gz", base_name),
         download_url: url,
-        display_name: Some("Source code (tar.gz)".to_string()),
+        display_name: Some(SOURCE_CODE_TAR_GZ.to_string()),
+        digest: None,
     }
 }
 
@@ -78,10 +158,50 @@ fn zipball_asset(url: String, base_name: &str) -> Asset {
     Asset {
         name: format!("{}.zip", base_name),
         download_url: url,
-        display_name: Some("Source code (zip)".to_string()),
+        display_name: Some(SOURCE_CODE_ZIP.to_string()),
+        digest: None,
     }
 }
 
 fn source_code(repository: &Repository, tag: &Tag) -> String {
     format!("{}-{}-source-code", repository.repo, tag.version(),)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1.4.2", "1.4.2"; "only version")]
+    #[test_case("v1.4.2", "1.4.2"; "v prefix")]
+    #[test_case("V1.4.2", "1.4.2"; "uppercase v prefix")]
+    #[test_case("nvim-v0.10.0", "0.10.0"; "name and v prefix")]
+    #[test_case("release-2024.01.31", "2024.01.31"; "name prefix")]
+    #[test_case("tool2-v1.0.0", "1.0.0"; "name with digit")]
+    #[test_case("v1.0.0-rc.1", "1.0.0-rc.1"; "prerelease")]
+    #[test_case("nightly", "nightly"; "no version")]
+    fn without_prefix(tag: &str, expected: &str) {
+        let result = Tag(tag.to_string()).without_prefix();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test_case("v1.4.2", "1.4.2"; "v prefix")]
+    #[test_case("nvim-v0.10.0", "nim-0.10.0"; "every v")]
+    #[test_case("release-2024.01.31", "release-2024.01.31"; "name prefix")]
+    fn version(tag: &str, expected: &str) {
+        let result = Tag(tag.to_string()).version();
+
+        assert_eq!(expected, result);
+    }
+
+    #[test_case("v1.4.2", vec!["1", "4", "2"]; "full version")]
+    #[test_case("nvim-v0.10", vec!["0", "10"]; "short version")]
+    #[test_case("v1.4.2-rc.1", vec!["1", "4", "2"]; "prerelease")]
+    #[test_case("nightly", vec![]; "no version")]
+    fn version_components(tag: &str, expected: Vec<&str>) {
+        let result = Tag(tag.to_string()).version_components();
+
+        assert_eq!(expected, result);
+    }
+}
@@ -3,6 +3,13 @@ use serde::Deserialize;
 #[derive(Deserialize, Debug)]
 pub struct ReleaseResponse {
     pub tag_name: String,
+    pub name: Option<String>,
+    /// Not available for draft releases
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
     pub tarball_url: String,
     pub zipball_url: String,
     pub assets: Vec<AssetResponse>,
@@ -12,4 +19,6 @@ pub struct ReleaseResponse {
 pub struct AssetResponse {
     pub name: String,
     pub browser_download_url: String,
+    /// e.g. `sha256:<hex>` (not available for assets uploaded before June 2025)
+    pub digest: Option<String>,
 }
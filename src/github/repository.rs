@@ -1,26 +1,100 @@
 use std::fmt::Formatter;
 use url::Url;
 
+pub const GITHUB_HOST: &str = "github.com";
+pub const GITLAB_HOST: &str = "gitlab.com";
+pub const CODEBERG_HOST: &str = "codeberg.org";
+const HTTPS: &str = "https";
+const HTTP: &str = "http";
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Repository {
+    /// GitLab groups can be nested (e.g. `group/subgroup`)
     pub owner: String,
     pub repo: String,
+    /// Self-hosted instance (e.g. GitHub Enterprise Server), `None` for the provider default host
+    pub host: Option<String>,
+    /// `http` only for self-hosted instances given with an `http://` URL
+    pub scheme: &'static str,
+    pub provider: Provider,
+}
+
+/// Service hosting the repository releases
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum Provider {
+    GitHub,
+    GitLab,
+    /// Gitea and its forks (e.g. Forgejo), Codeberg is the default host
+    Gitea,
+}
+
+impl Provider {
+    fn from_prefix(prefix: &str) -> Option<Provider> {
+        match prefix {
+            "github" => Some(Provider::GitHub),
+            "gitlab" => Some(Provider::GitLab),
+            "gitea" | "forgejo" | "codeberg" => Some(Provider::Gitea),
+            _ => None,
+        }
+    }
+
+    /// Prefix used to select the provider, e.g. `gitlab:owner/repo`
+    fn prefix(&self) -> Option<&str> {
+        match self {
+            Provider::GitHub => None,
+            Provider::GitLab => Some("gitlab"),
+            Provider::Gitea => Some("gitea"),
+        }
+    }
+
+    fn from_host(host: &str) -> Option<Provider> {
+        match host {
+            GITHUB_HOST | "www.github.com" => Some(Provider::GitHub),
+            GITLAB_HOST => Some(Provider::GitLab),
+            x if x.starts_with("gitlab.") => Some(Provider::GitLab),
+            CODEBERG_HOST => Some(Provider::Gitea),
+            _ => None,
+        }
+    }
+
+    pub fn default_host(&self) -> &str {
+        match self {
+            Provider::GitHub => GITHUB_HOST,
+            Provider::GitLab => GITLAB_HOST,
+            Provider::Gitea => CODEBERG_HOST,
+        }
+    }
+
+    fn allows_nested_owner(&self) -> bool {
+        match self {
+            Provider::GitHub | Provider::Gitea => false,
+            Provider::GitLab => true,
+        }
+    }
 }
 
 impl Repository {
     pub fn try_parse(src: &str) -> Result<Repository, String> {
+        let (provider, src) = match src.split_once(':') {
+            Some((prefix, rest)) => match Provider::from_prefix(prefix) {
+                Some(provider) => (Some(provider), rest),
+                None => (None, src),
+            },
+            None => (None, src),
+        };
+
         if src.is_empty() {
             return Err("Invalid repository. Cannot be empty".to_string());
         }
 
-        if src.starts_with("http://github.com") || src.starts_with("https://github.com") {
-            Self::parse_url(src)
+        if src.starts_with("http://") || src.starts_with("https://") {
+            Self::parse_url(src, provider)
         } else {
-            Self::parse(src)
+            Self::parse(src, provider.unwrap_or(Provider::GitHub))
         }
     }
 
-    fn parse(input: &str) -> Result<Repository, String> {
+    fn parse(input: &str, provider: Provider) -> Result<Repository, String> {
         if !input.contains('/') {
             return Err("Invalid repository. Use {owner}/{repo} format".to_string());
         }
@@ -28,43 +102,93 @@ impl Repository {
             .split('/')
             .filter(|x| !x.is_empty())
             .collect::<Vec<&str>>();
-        if parts.len() < 2 {
-            return Err("Invalid repository. Missing owner or repo".to_string());
-        }
-
-        Ok(Repository {
-            owner: parts[0].to_string(),
-            repo: parts[1].to_string(),
-        })
+        Self::from_parts(&parts, None, HTTPS, provider)
+            .ok_or_else(|| "Invalid repository. Missing owner or repo".to_string())
     }
 
-    fn parse_url(input: &str) -> Result<Repository, String> {
-        let github_url = Url::parse(input).map_err(|x| format!("Invalid repository URL: {}", x))?;
-        let parts = github_url
+    fn parse_url(input: &str, provider: Option<Provider>) -> Result<Repository, String> {
+        let url = Url::parse(input).map_err(|x| format!("Invalid repository URL: {}", x))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| "Invalid repository URL. Missing host".to_string())?;
+        let provider = provider
+            .or_else(|| Provider::from_host(host))
+            .unwrap_or(Provider::GitHub);
+        let is_default_host = host == provider.default_host() || host == "www.github.com";
+        let scheme = match url.scheme() {
+            HTTP if !is_default_host => HTTP,
+            _ => HTTPS,
+        };
+        let host = match url.port() {
+            None if is_default_host => None,
+            Some(port) => Some(format!("{}:{}", host, port)),
+            None => Some(host.to_string()),
+        };
+
+        // NOTE: GitLab pages are below `/-/` (e.g. /owner/repo/-/releases)
+        let parts = url
             .path()
             .split('/')
             .filter(|x| !x.is_empty())
+            .take_while(|x| *x != "-")
             .collect::<Vec<&str>>();
+        Self::from_parts(&parts, host, scheme, provider)
+            .ok_or_else(|| "Invalid repository URL. Missing owner or repo".to_string())
+    }
+
+    fn from_parts(
+        parts: &[&str],
+        host: Option<String>,
+        scheme: &'static str,
+        provider: Provider,
+    ) -> Option<Repository> {
         if parts.len() < 2 {
-            return Err("Invalid repository URL. Missing owner or repo".to_string());
+            return None;
         }
 
-        Ok(Repository {
-            owner: parts[0].to_string(),
-            repo: parts[1].to_string(),
+        let (owner, repo) = if provider.allows_nested_owner() {
+            let (repo, owner) = parts.split_last()?;
+            (owner.join("/"), repo.to_string())
+        } else {
+            (parts[0].to_string(), parts[1].to_string())
+        };
+        Some(Repository {
+            owner,
+            repo,
+            host,
+            scheme,
+            provider,
         })
     }
+
+    pub fn host(&self) -> &str {
+        self.host
+            .as_deref()
+            .unwrap_or_else(|| self.provider.default_host())
+    }
+
+    /// Scheme and host of the instance (e.g. `https://gitlab.com` or `http://ghe.example.com`)
+    pub fn base_url(&self) -> String {
+        format!("{}://{}", self.scheme, self.host())
+    }
 }
 
 impl std::fmt::Display for Repository {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}/{}", &self.owner, &self.repo)
+        if let Some(prefix) = self.provider.prefix() {
+            write!(f, "{}:", prefix)?;
+        }
+        match &self.host {
+            Some(_) => write!(f, "{}/{}/{}", self.base_url(), &self.owner, &self.repo),
+            None => write!(f, "{}/{}", &self.owner, &self.repo),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
 
     #[test]
     fn valid_repository() {
@@ -75,7 +199,10 @@ mod tests {
         assert_eq!(
             Ok(Repository {
                 owner: "foo".to_string(),
-                repo: "bar".to_string()
+                repo: "bar".to_string(),
+                host: None,
+                scheme: HTTPS,
+                provider: Provider::GitHub,
             }),
             result
         );
@@ -90,7 +217,10 @@ mod tests {
         assert_eq!(
             Ok(Repository {
                 owner: "foo".to_string(),
-                repo: "bar".to_string()
+                repo: "bar".to_string(),
+                host: None,
+                scheme: HTTPS,
+                provider: Provider::GitHub,
             }),
             result
         );
@@ -105,7 +235,91 @@ mod tests {
         assert_eq!(
             Ok(Repository {
                 owner: "foo".to_string(),
-                repo: "bar".to_string()
+                repo: "bar".to_string(),
+                host: None,
+                scheme: HTTPS,
+                provider: Provider::GitHub,
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn valid_repository_from_enterprise_url() {
+        let input = "https://ghe.example.com/foo/bar/releases";
+
+        let result = Repository::try_parse(input);
+
+        assert_eq!(
+            Ok(Repository {
+                owner: "foo".to_string(),
+                repo: "bar".to_string(),
+                host: Some("ghe.example.com".to_string()),
+                scheme: HTTPS,
+                provider: Provider::GitHub,
+            }),
+            result
+        );
+    }
+
+    #[test_case("foo/bar"; "github")]
+    #[test_case("https://ghe.example.com/foo/bar"; "enterprise")]
+    #[test_case("https://localhost:8443/foo/bar"; "enterprise with port")]
+    #[test_case("http://ghe.example.com/foo/bar"; "enterprise over http")]
+    #[test_case("gitlab:foo/bar"; "gitlab")]
+    #[test_case("gitlab:group/subgroup/bar"; "gitlab subgroup")]
+    #[test_case("gitlab:https://git.example.com/foo/bar"; "self-hosted gitlab")]
+    #[test_case("gitea:foo/bar"; "codeberg")]
+    #[test_case("gitea:https://git.example.com/foo/bar"; "self-hosted gitea")]
+    fn display_can_be_parsed(input: &str) {
+        let repository = Repository::try_parse(input).unwrap();
+
+        let result = Repository::try_parse(&repository.to_string());
+
+        assert_eq!(Ok(repository), result);
+        assert_eq!(input, result.unwrap().to_string());
+    }
+
+    #[test_case("gitlab:group/subgroup/bar"; "prefix")]
+    #[test_case("https://gitlab.com/group/subgroup/bar/-/releases/v1.0.0"; "url")]
+    fn valid_gitlab_repository(input: &str) {
+        let result = Repository::try_parse(input);
+
+        assert_eq!(
+            Ok(Repository {
+                owner: "group/subgroup".to_string(),
+                repo: "bar".to_string(),
+                host: None,
+                scheme: HTTPS,
+                provider: Provider::GitLab,
+            }),
+            result
+        );
+    }
+
+    #[test_case("https://gitlab.example.com/foo/bar", "gitlab.example.com"; "gitlab subdomain")]
+    #[test_case("gitlab:https://git.example.com/foo/bar", "git.example.com"; "prefix")]
+    fn self_hosted_gitlab_repository(input: &str, expected_host: &str) {
+        let result = Repository::try_parse(input).unwrap();
+
+        assert_eq!(Provider::GitLab, result.provider);
+        assert_eq!(Some(expected_host.to_string()), result.host);
+    }
+
+    #[test_case("codeberg:foo/bar", None; "codeberg prefix")]
+    #[test_case("https://codeberg.org/foo/bar/releases", None; "codeberg url")]
+    #[test_case("forgejo:https://git.example.com/foo/bar", Some("git.example.com"); "forgejo")]
+    #[test_case("gitea:https://git.example.com/foo/bar", Some("git.example.com"); "gitea")]
+    fn valid_gitea_repository(input: &str, expected_host: Option<&str>) {
+        let result = Repository::try_parse(input);
+
+        assert_eq!(
+            Ok(Repository {
+                owner: "foo".to_string(),
+                repo: "bar".to_string(),
+                host: expected_host.map(String::from),
+                scheme: HTTPS,
+                provider: Provider::Gitea,
             }),
             result
         );
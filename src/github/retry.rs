@@ -0,0 +1,151 @@
+use crate::github::error::GithubError;
+use std::time::Duration;
+
+const BASE_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+// NOTE: longer Retry-After values are better handled by failing (or --wait-for-rate-limit) than by sleeping
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// Exponential backoff with jitter for idempotent requests
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+}
+
+impl RetryPolicy {
+    pub fn new(retries: u32) -> Self {
+        Self { retries }
+    }
+
+    pub fn should_retry(&self, attempt: u32, error: &GithubError) -> bool {
+        attempt < self.retries && is_transient(error)
+    }
+
+    /// Delay before the given retry attempt (starting from 1).
+    /// `Retry-After` sent by the server, of any retried status, takes precedence up to [MAX_RETRY_AFTER]
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(x) = retry_after {
+            return x.min(MAX_RETRY_AFTER);
+        }
+
+        let exponential = BASE_DELAY
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(MAX_DELAY);
+        // NOTE: jitter avoids many clients retrying at the same time
+        let half = exponential / 2;
+        half + half.mul_f64(fastrand::f64())
+    }
+}
+
+/// Only errors that may succeed on a new attempt are retried (e.g. 401 and 404 never are)
+fn is_transient(error: &GithubError) -> bool {
+    match error {
+        GithubError::Http(error) => match error.as_ref() {
+            ureq::Error::StatusCode(status) => matches!(status, 408 | 500 | 502 | 503 | 504),
+            ureq::Error::Io(_)
+            | ureq::Error::Timeout(_)
+            | ureq::Error::ConnectionFailed
+            | ureq::Error::HostNotFound
+            | ureq::Error::Protocol(_) => true,
+            _ => false,
+        },
+        GithubError::SecondaryRateLimitExceeded { .. } => true,
+        _ => false,
+    }
+}
+
+/// Only the delay in seconds format is supported (e.g. `Retry-After: 120`), which is what GitHub uses
+pub fn retry_after(headers: &ureq::http::HeaderMap) -> Option<Duration> {
+    headers
+        .get("Retry-After")
+        .and_then(|x| x.to_str().ok())
+        .and_then(|x| x.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(502; "bad gateway")]
+    #[test_case(503; "service unavailable")]
+    #[test_case(429; "too many requests")]
+    fn retry_transient_status(status: u16) {
+        let policy = RetryPolicy::new(3);
+
+        assert!(policy.should_retry(0, &status_error(status)));
+    }
+
+    #[test_case(401; "unauthorized")]
+    #[test_case(403; "forbidden")]
+    #[test_case(404; "not found")]
+    #[test_case(416; "range not satisfiable")]
+    fn never_retry_status(status: u16) {
+        let policy = RetryPolicy::new(3);
+
+        assert!(!policy.should_retry(0, &status_error(status)));
+    }
+
+    #[test]
+    fn retry_connection_errors() {
+        let policy = RetryPolicy::new(3);
+
+        assert!(policy.should_retry(2, &GithubError::from(ureq::Error::ConnectionFailed)));
+    }
+
+    #[test]
+    fn no_more_retries() {
+        let policy = RetryPolicy::new(3);
+
+        assert!(!policy.should_retry(3, &GithubError::from(ureq::Error::ConnectionFailed)));
+    }
+
+    fn status_error(status: u16) -> GithubError {
+        GithubError::from_response(status, &ureq::http::HeaderMap::new())
+    }
+
+    #[test_case(1, Duration::from_millis(500), Duration::from_secs(1); "first attempt")]
+    #[test_case(3, Duration::from_secs(2), Duration::from_secs(4); "third attempt")]
+    #[test_case(20, Duration::from_secs(15), Duration::from_secs(30); "max delay")]
+    fn exponential_delay(attempt: u32, min: Duration, max: Duration) {
+        let policy = RetryPolicy::new(3);
+
+        let result = policy.delay(attempt, None);
+
+        assert!(
+            min <= result && result <= max,
+            "{:?} not in {:?}..={:?}",
+            result,
+            min,
+            max
+        );
+    }
+
+    #[test]
+    fn retry_after_has_precedence() {
+        let policy = RetryPolicy::new(3);
+
+        let result = policy.delay(1, Some(Duration::from_secs(60)));
+
+        assert_eq!(Duration::from_secs(60), result);
+    }
+
+    #[test]
+    fn retry_after_is_limited() {
+        let policy = RetryPolicy::new(3);
+
+        let result = policy.delay(1, Some(Duration::from_secs(24 * 60 * 60)));
+
+        assert_eq!(MAX_RETRY_AFTER, result);
+    }
+
+    #[test_case("120", Some(Duration::from_secs(120)); "seconds")]
+    #[test_case("Wed, 21 Oct 2026 07:28:00 GMT", None; "http date")]
+    fn parse_retry_after(value: &str, expected: Option<Duration>) {
+        let mut headers = ureq::http::HeaderMap::new();
+        headers.insert("Retry-After", value.parse().unwrap());
+
+        assert_eq!(expected, retry_after(&headers));
+    }
+}
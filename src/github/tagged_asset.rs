@@ -1,19 +1,162 @@
 use crate::github::release::{Asset, Tag};
 
+/// Convert asset names to version-free patterns and back.
+///
+/// Supported placeholders are:
+/// - `{tag}`: release tag with every `v` removed (e.g. `1.4.2-rc.1` for `v1.4.2-rc.1`)
+/// - `{tag_version}`: release tag without its prefix (e.g. `0.10.0` for `nvim-v0.10.0`)
+/// - `{version}`: numeric version (e.g. `1.4.2` for `v1.4.2-rc.1`)
+/// - `{major}`, `{minor}` and `{patch}`: components of the numeric version
 pub struct TaggedAsset;
 
+/// Placeholders and the values they are replaced with
+struct Placeholders(Vec<(&'static str, String)>);
+
+impl Placeholders {
+    fn new(tag: &Tag) -> Self {
+        let components = tag.version_components();
+        let mut values = vec![
+            (TaggedAsset::PLACEHOLDER, tag.version()),
+            ("{tag_version}", tag.without_prefix()),
+            ("{version}", components.join(".")),
+        ];
+        let names = ["{major}", "{minor}", "{patch}"];
+        values.extend(names.into_iter().zip(components));
+        Self(values)
+    }
+
+    fn replace(&self, pattern: &str, escape: impl Fn(&str) -> String) -> String {
+        self.0
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .fold(pattern.to_string(), |pattern, (placeholder, value)| {
+                pattern.replace(placeholder, &escape(value))
+            })
+    }
+}
+
 impl TaggedAsset {
-    const PLACEHOLDER: &'static str = "{tag}";
+    pub const PLACEHOLDER: &'static str = "{tag}";
 
+    /// Replace placeholders with the values of the tag. Placeholders not available
+    /// for the tag (e.g. `{patch}` for `v1.4`) are kept as they are
     pub fn tag(tag: &Tag, untagged: &str) -> String {
-        untagged.replace(Self::PLACEHOLDER, &tag.version())
+        Placeholders::new(tag).replace(untagged, str::to_string)
     }
 
+    /// Same as [TaggedAsset::tag], with the values escaped to be used in a regular expression
+    pub fn tag_regex(tag: &Tag, untagged: &str) -> String {
+        Placeholders::new(tag).replace(untagged, regex::escape)
+    }
+
+    /// Replace the version in the asset name with placeholders, trying the most specific form first:
+    /// the `{tag}` value, the tag without its prefix, the numeric version, underscore separated components
+    /// and the short `major.minor` version
     pub fn untag(tag: &Tag, asset: &Asset) -> String {
-        asset.name.replace(&tag.version(), Self::PLACEHOLDER)
+        let version = tag.version();
+        if !version.is_empty() && asset.name.contains(&version) {
+            return asset.name.replace(&version, Self::PLACEHOLDER);
+        }
+        Self::version_forms(tag)
+            .into_iter()
+            .find_map(|(form, placeholder)| replace_token(&asset.name, &form, &placeholder))
+            .unwrap_or_else(|| asset.name.clone())
+    }
+
+    /// Replace dates like `20240131` or `2024-01-31` with `*`, since they change on every release.
+    /// The pattern can match more than one asset, so it's only used when asked (`untag --wildcard-dates`)
+    pub fn wildcard_dates(untagged: &str) -> String {
+        let chars = untagged.chars().collect::<Vec<_>>();
+        let mut result = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let is_start = i == 0 || !chars[i - 1].is_ascii_digit();
+            match date_length(&chars[i..]).filter(|_| is_start) {
+                Some(length) => {
+                    result.push('*');
+                    i += length;
+                }
+                None => {
+                    result.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        result
+    }
+
+    /// Other ways the version is written in asset names, with the placeholders to use
+    fn version_forms(tag: &Tag) -> Vec<(String, String)> {
+        let placeholders = ["{major}", "{minor}", "{patch}"];
+        let components = tag.version_components();
+        let mut forms = vec![];
+        let without_prefix = tag.without_prefix();
+        if without_prefix != tag.0 && without_prefix != tag.version() {
+            forms.push((without_prefix, "{tag_version}".to_string()));
+        }
+        if components.len() < 2 {
+            return forms;
+        }
+        forms.push((components.join("."), "{version}".to_string()));
+        if components.len() <= placeholders.len() {
+            let placeholders = &placeholders[..components.len()];
+            forms.push((components.join("_"), placeholders.join("_")));
+        }
+        if components.len() > 2 {
+            forms.push((components[..2].join("."), "{major}.{minor}".to_string()));
+        }
+        forms
     }
 }
 
+/// Replace `token` when it isn't part of a longer number (e.g. `1.4` in `1.40`)
+fn replace_token(name: &str, token: &str, replacement: &str) -> Option<String> {
+    let is_digit_at = |i: Option<char>| i.is_some_and(|c| c.is_ascii_digit());
+    let mut result = String::new();
+    let mut rest = name;
+    let mut found = false;
+    while let Some(start) = rest.find(token) {
+        let end = start + token.len();
+        let before = rest[..start].chars().next_back();
+        let after = rest[end..].chars().next();
+        // NOTE: a dot followed by a digit means a longer version (e.g. `1.4` in `1.4.2`)
+        let after_next = rest[end..].chars().nth(1);
+        let is_longer_version = after == Some('.') && is_digit_at(after_next);
+        if is_digit_at(before) || is_digit_at(after) || is_longer_version {
+            result.push_str(&rest[..end]);
+        } else {
+            result.push_str(&rest[..start]);
+            result.push_str(replacement);
+            found = true;
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    found.then_some(result)
+}
+
+/// Length of the date at the beginning of `chars`, if any
+fn date_length(chars: &[char]) -> Option<usize> {
+    let digits = |range: std::ops::Range<usize>| {
+        chars
+            .get(range.clone())
+            .is_some_and(|x| x.iter().all(char::is_ascii_digit))
+    };
+    let is_year = digits(0..4) && matches!(chars[..2], ['1', '9'] | ['2', '0']);
+    let is_end = |i: usize| chars.get(i).is_none_or(|c| !c.is_ascii_digit());
+    if !is_year {
+        return None;
+    }
+    if digits(4..8) && is_end(8) {
+        return Some(8);
+    }
+    let is_separated = chars.get(4) == Some(&'-') && chars.get(7) == Some(&'-');
+    if is_separated && digits(5..7) && digits(8..10) && is_end(10) {
+        return Some(10);
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,6 +171,49 @@ mod tests {
         assert_eq!(expected.to_string(), result);
     }
 
+    #[test_case("v1.4.2", "tool_1_4_2_linux.tar.gz", "tool_{major}_{minor}_{patch}_linux.tar.gz"; "underscores")]
+    #[test_case("v1.4.2", "tool-1.4-linux.tar.gz", "tool-{major}.{minor}-linux.tar.gz"; "short version")]
+    #[test_case("v1.4.2-rc.1", "tool-1.4.2-linux.tar.gz", "tool-{version}-linux.tar.gz"; "numeric version")]
+    #[test_case("nvim-v0.10.0", "nvim-0.10.0-linux.tar.gz", "nvim-{tag_version}-linux.tar.gz"; "prefixed tag")]
+    #[test_case("nvim-v0.10.0", "nvim-nim-0.10.0-linux.tar.gz", "nvim-{tag}-linux.tar.gz"; "tag with every v removed")]
+    #[test_case("v1.4.2", "tool-1.4.2-20240131.tar.gz", "tool-{tag}-20240131.tar.gz"; "version and date")]
+    #[test_case("v1.4.2", "tool-1.40-linux.tar.gz", "tool-1.40-linux.tar.gz"; "longer number")]
+    fn untag_version_forms(tag: &str, asset_name: &str, expected: &str) {
+        let result = TaggedAsset::untag(&tag_for(tag), &asset_for(asset_name));
+
+        assert_eq!(expected.to_string(), result);
+    }
+
+    #[test_case("tool-{tag}-20240131.tar.gz", "tool-{tag}-*.tar.gz"; "compact date")]
+    #[test_case("tool-2024-01-31-linux.tar.gz", "tool-*-linux.tar.gz"; "separated date")]
+    #[test_case("tool-120240131.tar.gz", "tool-120240131.tar.gz"; "longer number")]
+    fn wildcard_dates(untagged: &str, expected: &str) {
+        let result = TaggedAsset::wildcard_dates(untagged);
+
+        assert_eq!(expected.to_string(), result);
+    }
+
+    #[test_case("tool_{major}_{minor}_{patch}.tar.gz", "tool_1_4_2.tar.gz"; "components")]
+    #[test_case("tool-{version}.tar.gz", "tool-1.4.2.tar.gz"; "numeric version")]
+    #[test_case("tool-{major}.{minor}.tar.gz", "tool-1.4.tar.gz"; "short version")]
+    #[test_case("tool-{tag}.tar.gz", "tool-1.4.2-rc.1.tar.gz"; "tag")]
+    #[test_case("tool-{tag_version}.tar.gz", "tool-1.4.2-rc.1.tar.gz"; "tag without prefix")]
+    fn tag_placeholders(untagged: &str, expected: &str) {
+        let result = TaggedAsset::tag(&tag_for("v1.4.2-rc.1"), untagged);
+
+        assert_eq!(expected.to_string(), result);
+    }
+
+    #[test]
+    fn untag_can_be_tagged() {
+        let tag = tag_for("v1.4.2");
+        let asset = asset_for("tool_1_4_2_linux.tar.gz");
+
+        let result = TaggedAsset::tag(&tag, &TaggedAsset::untag(&tag, &asset));
+
+        assert_eq!(asset.name, result);
+    }
+
     #[test]
     fn untag_no_tag_in_asset_name() {
         let result = TaggedAsset::untag(&tag_for("v1.5.3"), &asset_for("file-linux.deb"));
@@ -59,6 +245,7 @@ mod tests {
         Asset {
             name: name.to_string(),
             download_url: "ANY_DOWNLOAD_URL".to_string(),
+            digest: None,
             display_name: None,
         }
     }
@@ -1,3 +1,4 @@
+use crate::installer::binary_format;
 use crate::installer::destination::Destination;
 use crate::installer::error::{
     ArchiveError, ArchiveErrorType, ArchiveInstallerError, InstallError, InstallErrorMapErr,
@@ -35,16 +36,80 @@ impl ArchiveInstaller {
             all_executables,
         );
 
+        let messages = successes
+            .iter()
+            .map(|x| format!("Extracted archive executable to '{}'", x.display()))
+            .collect::<Vec<_>>();
+
         if !failures.is_empty() {
             return Err(InstallError::Archive(ArchiveInstallerError {
-                successes,
+                successes: messages,
                 failures,
             }));
         }
 
         Self::cleanup(&temp_dir)?;
 
-        Ok(InstallOutput::new(successes.join("\n").to_string()))
+        Ok(InstallOutput::new(
+            messages.join("\n").to_string(),
+            successes,
+        ))
+    }
+
+    /// Extract the archive in a temporary directory to find the executables that would be installed
+    pub fn plan<F>(
+        extract_files: F,
+        file_info: &SupportedFileInfo,
+        destination: &Destination,
+        executables_to_install: Vec<Executable>,
+    ) -> Result<ArchivePlan, InstallError>
+    where
+        F: FnOnce(&Path, &Path) -> Result<(), InstallError>,
+    {
+        let temp_dir = Self::create_temp_dir()?;
+        let plan = extract_files(&file_info.path, &temp_dir).map(|_| {
+            let all_executables = Self::executables_from(&temp_dir);
+            let executables = executables_to_install
+                .into_iter()
+                .map(|executable| {
+                    Self::find_executable(&temp_dir, &executable, &all_executables)
+                        .map(|x| {
+                            let to = Self::destination_path(&x, destination);
+                            (relative_to(&x.path, &temp_dir), to)
+                        })
+                        .map_err(|error| ArchiveError(executable.name(), error))
+                })
+                .collect();
+            ArchivePlan {
+                files: Self::files_from(&temp_dir),
+                executables,
+            }
+        });
+        Self::cleanup(&temp_dir)?;
+        plan
+    }
+
+    /// Extract the archive in a temporary directory to list its files and executables
+    pub fn inspect<F>(
+        extract_files: F,
+        file_info: &SupportedFileInfo,
+    ) -> Result<ArchiveContents, InstallError>
+    where
+        F: FnOnce(&Path, &Path) -> Result<(), InstallError>,
+    {
+        let temp_dir = Self::create_temp_dir()?;
+        let contents = extract_files(&file_info.path, &temp_dir).map(|_| ArchiveContents {
+            files: Self::files_from(&temp_dir),
+            executables: Self::executables_from(&temp_dir)
+                .iter()
+                .map(|x| InspectedExecutable {
+                    path: relative_to(&x.path, &temp_dir),
+                    format: binary_format::detect(&x.path),
+                })
+                .collect(),
+        });
+        Self::cleanup(&temp_dir)?;
+        contents
     }
 
     fn create_temp_dir() -> Result<PathBuf, InstallError> {
@@ -52,15 +117,7 @@ impl ArchiveInstaller {
     }
 
     fn all_executables_from(directory: &Path) -> Result<Vec<ExecutableFile>, InstallError> {
-        let ignore_error = |result: walkdir::Result<walkdir::DirEntry>| result.ok();
-
-        let executables: Vec<_> = WalkDir::new(directory)
-            .max_depth(3)
-            .into_iter()
-            .filter_map(ignore_error)
-            .filter(Self::is_executable)
-            .map(ExecutableFile::from_file)
-            .collect();
+        let executables = Self::executables_from(directory);
 
         if executables.is_empty() {
             return Err(InstallError::NoExecutables);
@@ -70,12 +127,33 @@ impl ArchiveInstaller {
         Ok(executables)
     }
 
+    fn executables_from(directory: &Path) -> Vec<ExecutableFile> {
+        WalkDir::new(directory)
+            .max_depth(3)
+            .into_iter()
+            .filter_map(ignore_error)
+            .filter(Self::is_executable)
+            .map(ExecutableFile::from_file)
+            .collect()
+    }
+
+    /// All files and directories in the archive, relative to its root
+    fn files_from(directory: &Path) -> Vec<PathBuf> {
+        WalkDir::new(directory)
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(ignore_error)
+            .map(|x| relative_to(x.path(), directory))
+            .collect()
+    }
+
     fn try_install_executables(
         temp_dir: &Path,
         destination: &Destination,
         executables_to_install: Vec<Executable>,
         all_executables: Vec<ExecutableFile>,
-    ) -> (Vec<String>, Vec<ArchiveError>) {
+    ) -> (Vec<PathBuf>, Vec<ArchiveError>) {
         executables_to_install
             .into_iter()
             .map(|executable| {
@@ -84,12 +162,6 @@ impl ArchiveInstaller {
                         Self::copy_executable_to_destination(executable, destination)
                     })
                     .map_err(|error| ArchiveError(executable.name(), error))
-                    .map(|destination_path| {
-                        format!(
-                            "Extracted archive executable to '{}'",
-                            destination_path.display()
-                        )
-                    })
             })
             .partition_map(|result| match result {
                 Ok(x) => Either::Left(x),
@@ -147,10 +219,7 @@ impl ArchiveInstaller {
         executable: ExecutableFile,
         destination: &Destination,
     ) -> Result<PathBuf, ArchiveErrorType> {
-        let to = match destination {
-            Destination::Directory(dir) => dir.join(executable.name),
-            Destination::File(file) => file.clone(),
-        };
+        let to = Self::destination_path(&executable, destination);
 
         std::fs::copy(&executable.path, &to)
             .map(|_| ())
@@ -161,11 +230,50 @@ impl ArchiveInstaller {
         Ok(to)
     }
 
+    fn destination_path(executable: &ExecutableFile, destination: &Destination) -> PathBuf {
+        match destination {
+            Destination::Directory(dir) => dir.join(&executable.name),
+            Destination::File(file) => file.clone(),
+        }
+    }
+
     fn cleanup(temp_dir: &Path) -> Result<(), InstallError> {
         std::fs::remove_dir_all(temp_dir).map_fatal_err("Error deleting temp dir".into())
     }
 }
 
+/// What installing an archive would do
+#[derive(Debug)]
+pub struct ArchivePlan {
+    pub files: Vec<PathBuf>,
+    /// Path in the archive and destination path of each executable to install
+    pub executables: Vec<Result<(PathBuf, PathBuf), ArchiveError>>,
+}
+
+/// Files of an archive and the executables found by the installer
+#[derive(Debug)]
+pub struct ArchiveContents {
+    pub files: Vec<PathBuf>,
+    pub executables: Vec<InspectedExecutable>,
+}
+
+#[derive(Debug)]
+pub struct InspectedExecutable {
+    pub path: PathBuf,
+    /// Executable format and architecture, none for scripts
+    pub format: Option<String>,
+}
+
+fn ignore_error(result: walkdir::Result<walkdir::DirEntry>) -> Option<walkdir::DirEntry> {
+    result.ok()
+}
+
+fn relative_to(path: &Path, base_directory: &Path) -> PathBuf {
+    path.strip_prefix(base_directory)
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
 #[cfg(target_family = "unix")]
 fn is_executable_file(_: &Path, metadata: std::fs::Metadata) -> bool {
     metadata.permissions().mode() & 0o111 != 0
@@ -443,6 +551,80 @@ mod tests {
         assert_file_exists(executable_path(&destination_dir, "my-executable"))
     }
 
+    #[test]
+    fn plan_does_not_write_to_destination() {
+        let destination_dir = temp_dir("plan_does_not_write_to_destination");
+        let destination = Destination::Directory(destination_dir.clone());
+
+        let result = ArchiveInstaller::plan(
+            |_, temp_dir| {
+                let nested_dir = create_dir(temp_dir, "nested");
+                create_file(&nested_dir, "README.md");
+                create_executable_file(&nested_dir, "my-tool");
+                Ok(())
+            },
+            &any_file_info(),
+            &destination,
+            vec![
+                Executable::Automatic(executable_name("my-tool")),
+                Executable::Selected(executable_name("missing")),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                PathBuf::from("nested"),
+                PathBuf::from("nested").join("README.md"),
+                PathBuf::from("nested").join(executable_name("my-tool")),
+            ],
+            result.files
+        );
+        assert_eq!(
+            vec![
+                Ok((
+                    PathBuf::from("nested").join(executable_name("my-tool")),
+                    executable_path(&destination_dir, "my-tool")
+                )),
+                Err(ArchiveError(
+                    executable_name("missing"),
+                    ArchiveErrorType::ExecutableNotFound
+                )),
+            ],
+            result.executables
+        );
+        assert!(!executable_path(&destination_dir, "my-tool").exists());
+    }
+
+    #[test]
+    fn inspect_lists_files_and_executables() {
+        let result = ArchiveInstaller::inspect(
+            |_, temp_dir| {
+                let nested_dir = create_dir(temp_dir, "nested");
+                create_file(&nested_dir, "README.md");
+                create_executable_file(&nested_dir, "my-tool");
+                Ok(())
+            },
+            &any_file_info(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                PathBuf::from("nested"),
+                PathBuf::from("nested").join("README.md"),
+                PathBuf::from("nested").join(executable_name("my-tool")),
+            ],
+            result.files
+        );
+        assert_eq!(1, result.executables.len());
+        assert_eq!(
+            PathBuf::from("nested").join(executable_name("my-tool")),
+            result.executables[0].path
+        );
+        assert_eq!(None, result.executables[0].format);
+    }
+
     fn any_automatic_executable_name() -> Executable {
         Executable::Automatic(executable_name("ANY_EXECUTABLE_NAME"))
     }
@@ -0,0 +1,199 @@
+use std::io::Read;
+use std::path::Path;
+
+// NOTE: big enough for ELF, Mach-O (including universal binaries) and PE headers
+const HEADER_SIZE: u64 = 4096;
+
+/// Executable format and architecture (e.g. `ELF 64-bit x86_64`), none for scripts and unknown formats
+pub fn detect(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    detect_from(file)
+}
+
+pub fn detect_from(reader: impl Read) -> Option<String> {
+    let mut header = Vec::new();
+    reader.take(HEADER_SIZE).read_to_end(&mut header).ok()?;
+    from_header(&header)
+}
+
+fn from_header(header: &[u8]) -> Option<String> {
+    match header.get(..4)? {
+        [0x7F, b'E', b'L', b'F'] => elf(header),
+        [0xFE, 0xED, 0xFA, 0xCE] | [0xFE, 0xED, 0xFA, 0xCF] => {
+            macho_cpu(read_u32(header, 4, Endian::Big)?).map(|x| format!("Mach-O {}", x))
+        }
+        [0xCE, 0xFA, 0xED, 0xFE] | [0xCF, 0xFA, 0xED, 0xFE] => {
+            macho_cpu(read_u32(header, 4, Endian::Little)?).map(|x| format!("Mach-O {}", x))
+        }
+        [0xCA, 0xFE, 0xBA, 0xBE] => macho_universal(header),
+        [b'M', b'Z', _, _] => pe(header),
+        _ => None,
+    }
+}
+
+// DOCS: https://en.wikipedia.org/wiki/Executable_and_Linkable_Format#ELF_header
+fn elf(header: &[u8]) -> Option<String> {
+    let bits = match header.get(4)? {
+        1 => "32-bit",
+        2 => "64-bit",
+        _ => return None,
+    };
+    let endian = match header.get(5)? {
+        1 => Endian::Little,
+        2 => Endian::Big,
+        _ => return None,
+    };
+    let machine = match read_u16(header, 18, endian)? {
+        0x03 => "x86",
+        0x08 => "mips",
+        0x14 => "powerpc",
+        0x15 => "powerpc64",
+        0x16 => "s390x",
+        0x28 => "arm",
+        0x3E => "x86_64",
+        0xB7 => "aarch64",
+        0xF3 => "riscv",
+        0x102 => "loongarch",
+        _ => "unknown architecture",
+    };
+    Some(format!("ELF {} {}", bits, machine))
+}
+
+// DOCS: https://en.wikipedia.org/wiki/Mach-O#Mach-O_header
+fn macho_cpu(cpu_type: u32) -> Option<&'static str> {
+    const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+    let cpu = match cpu_type {
+        7 => "x86",
+        x if x == 7 | CPU_ARCH_ABI64 => "x86_64",
+        12 => "arm",
+        x if x == 12 | CPU_ARCH_ABI64 => "arm64",
+        18 => "powerpc",
+        x if x == 18 | CPU_ARCH_ABI64 => "powerpc64",
+        _ => return None,
+    };
+    Some(cpu)
+}
+
+/// Universal binaries contain one executable for each architecture
+fn macho_universal(header: &[u8]) -> Option<String> {
+    const FAT_ARCH_SIZE: usize = 20;
+    let count = read_u32(header, 4, Endian::Big)? as usize;
+    // NOTE: Java class files share the same magic number, but their version is always greater than 30
+    if count == 0 || count > 30 {
+        return None;
+    }
+    let architectures = (0..count)
+        .map(|i| read_u32(header, 8 + i * FAT_ARCH_SIZE, Endian::Big).and_then(macho_cpu))
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("Mach-O universal ({})", architectures.join(", ")))
+}
+
+// DOCS: https://learn.microsoft.com/en-us/windows/win32/debug/pe-format
+fn pe(header: &[u8]) -> Option<String> {
+    let pe_offset = read_u32(header, 0x3C, Endian::Little)? as usize;
+    if header.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+        return None;
+    }
+    let machine = match read_u16(header, pe_offset + 4, Endian::Little)? {
+        0x014C => "x86",
+        0x8664 => "x86_64",
+        0x01C0 | 0x01C4 => "arm",
+        0xAA64 => "aarch64",
+        _ => "unknown architecture",
+    };
+    Some(format!("PE {}", machine))
+}
+
+#[derive(Clone, Copy)]
+enum Endian {
+    Little,
+    Big,
+}
+
+fn read_u16(bytes: &[u8], offset: usize, endian: Endian) -> Option<u16> {
+    let x: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    Some(match endian {
+        Endian::Little => u16::from_le_bytes(x),
+        Endian::Big => u16::from_be_bytes(x),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, endian: Endian) -> Option<u32> {
+    let x: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(match endian {
+        Endian::Little => u32::from_le_bytes(x),
+        Endian::Big => u32::from_be_bytes(x),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(elf_header(2, 1, [0x3E, 0x00]), "ELF 64-bit x86_64"; "x86_64")]
+    #[test_case(elf_header(2, 1, [0xB7, 0x00]), "ELF 64-bit aarch64"; "aarch64")]
+    #[test_case(elf_header(1, 1, [0x28, 0x00]), "ELF 32-bit arm"; "arm")]
+    #[test_case(elf_header(2, 2, [0x00, 0x16]), "ELF 64-bit s390x"; "big endian")]
+    fn elf_architecture(header: Vec<u8>, expected: &str) {
+        assert_eq!(Some(expected.to_string()), from_header(&header));
+    }
+
+    #[test_case(vec![0xCF, 0xFA, 0xED, 0xFE, 0x07, 0x00, 0x00, 0x01], "Mach-O x86_64"; "x86_64")]
+    #[test_case(vec![0xCF, 0xFA, 0xED, 0xFE, 0x0C, 0x00, 0x00, 0x01], "Mach-O arm64"; "arm64")]
+    #[test_case(vec![0xFE, 0xED, 0xFA, 0xCE, 0x00, 0x00, 0x00, 0x12], "Mach-O powerpc"; "big endian")]
+    fn macho_architecture(header: Vec<u8>, expected: &str) {
+        assert_eq!(Some(expected.to_string()), from_header(&header));
+    }
+
+    #[test]
+    fn macho_universal_architectures() {
+        let mut header = vec![0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x00, 0x00, 0x02];
+        header.extend(fat_arch(0x0100_0007));
+        header.extend(fat_arch(0x0100_000C));
+
+        let result = from_header(&header);
+
+        assert_eq!(Some("Mach-O universal (x86_64, arm64)".to_string()), result);
+    }
+
+    #[test]
+    fn java_class_file_is_not_universal_binary() {
+        let header = vec![0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x00, 0x00, 0x41];
+
+        assert_eq!(None, from_header(&header));
+    }
+
+    #[test]
+    fn pe_architecture() {
+        let mut header = vec![0; 0x80];
+        header[..2].copy_from_slice(b"MZ");
+        header[0x3C] = 0x40;
+        header[0x40..0x44].copy_from_slice(b"PE\0\0");
+        header[0x44..0x46].copy_from_slice(&[0x64, 0x86]);
+
+        assert_eq!(Some("PE x86_64".to_string()), from_header(&header));
+    }
+
+    #[test_case(b"#!/bin/sh\necho hello\n"; "script")]
+    #[test_case(b"\x7FELF"; "truncated header")]
+    #[test_case(b""; "empty file")]
+    fn unknown_format(header: &[u8]) {
+        assert_eq!(None, from_header(header));
+    }
+
+    fn elf_header(class: u8, data: u8, machine: [u8; 2]) -> Vec<u8> {
+        let mut header = vec![0; 20];
+        header[..4].copy_from_slice(&[0x7F, b'E', b'L', b'F']);
+        header[4] = class;
+        header[5] = data;
+        header[18..20].copy_from_slice(&machine);
+        header
+    }
+
+    fn fat_arch(cpu_type: u32) -> Vec<u8> {
+        let mut arch = cpu_type.to_be_bytes().to_vec();
+        arch.extend([0; 16]);
+        arch
+    }
+}
@@ -2,15 +2,20 @@ use crate::installer::error::{InstallError, InstallErrorMapErr};
 use std::process::{Command, Output};
 
 pub fn exec_command(name: &str, command: &mut Command) -> Result<(), InstallError> {
+    command_output(name, command).map(|_| ())
+}
+
+/// Standard output of the command, when it succeeds
+pub fn command_output(name: &str, command: &mut Command) -> Result<String, InstallError> {
     command
         .output()
         .map_fatal_err(format!("An error occurred executing '{}'", name))
         .and_then(|output| handle_command_output(name, output))
 }
 
-fn handle_command_output(name: &str, output: Output) -> Result<(), InstallError> {
+fn handle_command_output(name: &str, output: Output) -> Result<String, InstallError> {
     if output.status.success() {
-        Ok(())
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
     } else {
         Err(InstallError::Fatal(format!(
             "An error occurred while executing (status: {}):\n  {}",
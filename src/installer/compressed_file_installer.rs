@@ -5,7 +5,7 @@ use std::path::PathBuf;
 use crate::installer::destination::Destination;
 use crate::installer::error::InstallErrorMapErr;
 use crate::installer::executable::{Executable, set_executable_permissions};
-use crate::installer::file::SupportedFileInfo;
+use crate::installer::file::{Compression, SupportedFileInfo};
 use crate::installer::result::{InstallOutput, InstallerResult};
 
 pub struct CompressedFileInstaller;
@@ -17,7 +17,7 @@ impl CompressedFileInstaller {
         _executables: Vec<Executable>,
     ) -> InstallerResult {
         Self::decompress_and_move(
-            |file| Box::new(flate2::read::GzDecoder::new(file)),
+            |file| Self::decoder(&Compression::Gz, file),
             file_info,
             destination,
         )
@@ -29,7 +29,7 @@ impl CompressedFileInstaller {
         _executables: Vec<Executable>,
     ) -> InstallerResult {
         Self::decompress_and_move(
-            |file| Box::new(xz2::read::XzDecoder::new(file)),
+            |file| Self::decoder(&Compression::Xz, file),
             file_info,
             destination,
         )
@@ -41,12 +41,20 @@ impl CompressedFileInstaller {
         _executables: Vec<Executable>,
     ) -> InstallerResult {
         Self::decompress_and_move(
-            |file| Box::new(bzip2::read::BzDecoder::new(file)),
+            |file| Self::decoder(&Compression::Bz2, file),
             file_info,
             destination,
         )
     }
 
+    pub fn decoder(compression: &Compression, file: File) -> Box<dyn Read> {
+        match compression {
+            Compression::Gz => Box::new(flate2::read::GzDecoder::new(file)),
+            Compression::Xz => Box::new(xz2::read::XzDecoder::new(file)),
+            Compression::Bz2 => Box::new(bzip2::read::BzDecoder::new(file)),
+        }
+    }
+
     fn decompress_and_move<D>(
         decode: D,
         file_info: SupportedFileInfo,
@@ -60,10 +68,7 @@ impl CompressedFileInstaller {
 
         let mut stream = decode(compressed_file);
 
-        let executable_path = match destination {
-            Destination::Directory(dir) => dir.join(executable_name(&file_info)),
-            Destination::File(file) => file,
-        };
+        let executable_path = Self::executable_path(&file_info, destination);
         let mut destination_file = File::create(&executable_path)
             .map_fatal_err(format!("Error creating {}", executable_path.display()))?;
 
@@ -72,10 +77,21 @@ impl CompressedFileInstaller {
 
         set_executable_permissions(&executable_path)?;
 
-        Ok(InstallOutput::new(format!(
-            "Extracted compressed executable to '{}'",
-            executable_path.display()
-        )))
+        Ok(InstallOutput::new(
+            format!(
+                "Extracted compressed executable to '{}'",
+                executable_path.display()
+            ),
+            vec![executable_path],
+        ))
+    }
+
+    /// Path of the decompressed executable
+    pub fn executable_path(file_info: &SupportedFileInfo, destination: Destination) -> PathBuf {
+        match destination {
+            Destination::Directory(dir) => dir.join(executable_name(file_info)),
+            Destination::File(file) => file,
+        }
     }
 }
 
@@ -20,6 +20,11 @@ impl DebianInstaller {
             DPKG,
             Command::new(DPKG).arg("--install").arg(file_info.path),
         )
-        .map(|_| InstallOutput::new(format!("Debian package '{}' installed", file_info.name)))
+        .map(|_| {
+            InstallOutput::new(
+                format!("Debian package '{}' installed", file_info.name),
+                vec![],
+            )
+        })
     }
 }
@@ -3,6 +3,7 @@ use crate::installer::error::InstallErrorMapErr;
 use crate::installer::executable::{Executable, set_executable_permissions};
 use crate::installer::file::SupportedFileInfo;
 use crate::installer::result::{InstallOutput, InstallerResult};
+use std::path::PathBuf;
 
 pub struct ExecutableFileInstaller;
 
@@ -12,10 +13,7 @@ impl ExecutableFileInstaller {
         destination: Destination,
         _executables: Vec<Executable>,
     ) -> InstallerResult {
-        let executable_path = match destination {
-            Destination::Directory(dir) => dir.join(file_info.name),
-            Destination::File(file) => file,
-        };
+        let executable_path = Self::executable_path(&file_info, destination);
 
         std::fs::copy(&file_info.path, &executable_path).map_fatal_err(format!(
             "Error copying {} to {}",
@@ -25,9 +23,17 @@ impl ExecutableFileInstaller {
 
         set_executable_permissions(&executable_path)?;
 
-        Ok(InstallOutput::new(format!(
-            "Extracted executable to '{}'",
-            executable_path.display()
-        )))
+        Ok(InstallOutput::new(
+            format!("Extracted executable to '{}'", executable_path.display()),
+            vec![executable_path],
+        ))
+    }
+
+    /// Path of the copied executable
+    pub fn executable_path(file_info: &SupportedFileInfo, destination: Destination) -> PathBuf {
+        match destination {
+            Destination::Directory(dir) => dir.join(&file_info.name),
+            Destination::File(file) => file,
+        }
     }
 }
@@ -140,6 +140,20 @@ impl Display for Compression {
     }
 }
 
+impl Display for FileType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileType::Debian => f.write_str("debian package"),
+            FileType::Rpm => f.write_str("rpm package"),
+            FileType::TarArchive(compression) => write!(f, "tar archive ({})", compression),
+            FileType::ZipArchive => f.write_str("zip archive"),
+            FileType::SevenZipArchive => f.write_str("7z archive"),
+            FileType::CompressedFile(compression) => write!(f, "compressed file ({})", compression),
+            FileType::ExecutableFile => f.write_str("executable file"),
+        }
+    }
+}
+
 impl FileInfo {
     pub fn new(name: &str, path: &Path) -> Self {
         FileInfo {
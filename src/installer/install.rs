@@ -1,7 +1,10 @@
+use crate::installer::archive_installer::{ArchiveContents, ArchiveInstaller, ArchivePlan};
+use crate::installer::binary_format;
+use crate::installer::command::command_output;
 use crate::installer::compressed_file_installer::CompressedFileInstaller;
 use crate::installer::debian_installer::DebianInstaller;
 use crate::installer::destination::Destination;
-use crate::installer::error::InstallError;
+use crate::installer::error::{InstallError, InstallErrorMapErr};
 use crate::installer::executable::Executable;
 use crate::installer::executable_file_installer::ExecutableFileInstaller;
 use crate::installer::file::{Compression, FileInfo, FileType, SupportedFileInfo, validate_file};
@@ -10,7 +13,11 @@ use crate::installer::rpm_installer::RpmInstaller;
 use crate::installer::seven_zip_archive_installer::SevenZipArchiveInstaller;
 use crate::installer::tar_archive_installer::TarArchiveInstaller;
 use crate::installer::zip_archive_installer::ZipArchiveInstaller;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const DPKG_DEB: &str = "dpkg-deb";
+const RPM: &str = "rpm";
 
 pub fn install(
     asset_name: String,
@@ -21,7 +28,108 @@ pub fn install(
     let file_info = file_info_from(&asset_name, source).and_then(validate_file)?;
     let installer = find_installer_for(&file_info.file_type);
 
-    installer(file_info, destination, executables)
+    (installer.run)(file_info, destination, executables)
+}
+
+/// What `install` would do, without writing anything to the destination
+pub fn plan(
+    asset_name: &str,
+    source: &Path,
+    destination: Destination,
+    executables: Vec<Executable>,
+) -> Result<InstallPlan, InstallError> {
+    let file_info = file_info_from(asset_name, source).and_then(validate_file)?;
+    let installer = find_installer_for(&file_info.file_type);
+
+    let target = match (installer.extract, &file_info.file_type) {
+        (Some(extract), _) => InstallTarget::Archive(ArchiveInstaller::plan(
+            extract,
+            &file_info,
+            &destination,
+            executables,
+        )?),
+        (None, FileType::Debian | FileType::Rpm) => InstallTarget::PackageManager,
+        (None, FileType::CompressedFile(_)) => InstallTarget::Executable(
+            CompressedFileInstaller::executable_path(&file_info, destination),
+        ),
+        (None, _) => InstallTarget::Executable(ExecutableFileInstaller::executable_path(
+            &file_info,
+            destination,
+        )),
+    };
+    Ok(InstallPlan {
+        installer: installer.name,
+        target,
+    })
+}
+
+#[derive(Debug)]
+pub struct InstallPlan {
+    pub installer: &'static str,
+    pub target: InstallTarget,
+}
+
+#[derive(Debug)]
+pub enum InstallTarget {
+    /// dpkg/rpm decide where the package files are installed
+    PackageManager,
+    Executable(PathBuf),
+    Archive(ArchivePlan),
+}
+
+/// File type and contents of a file, without installing it
+pub fn inspect(asset_name: &str, source: &Path) -> Result<Inspection, InstallError> {
+    let file_info = file_info_from(asset_name, source).and_then(validate_file)?;
+    let installer = find_installer_for(&file_info.file_type);
+
+    let contents = match (installer.extract, &file_info.file_type) {
+        (Some(extract), _) => {
+            InspectedContents::Archive(ArchiveInstaller::inspect(extract, &file_info)?)
+        }
+        (None, FileType::Debian) => InspectedContents::Package(package_metadata(
+            DPKG_DEB,
+            Command::new(DPKG_DEB).arg("--field").arg(&file_info.path),
+        )),
+        (None, FileType::Rpm) => InspectedContents::Package(package_metadata(
+            RPM,
+            Command::new(RPM)
+                .arg("--query")
+                .arg("--info")
+                .arg("--package")
+                .arg(&file_info.path),
+        )),
+        (None, FileType::CompressedFile(compression)) => {
+            let file = std::fs::File::open(&file_info.path)
+                .map_fatal_err(format!("Error opening {}", file_info.path.display()))?;
+            InspectedContents::Executable(binary_format::detect_from(
+                CompressedFileInstaller::decoder(compression, file),
+            ))
+        }
+        (None, _) => InspectedContents::Executable(binary_format::detect(&file_info.path)),
+    };
+    Ok(Inspection {
+        file_type: file_info.file_type.to_string(),
+        contents,
+    })
+}
+
+#[derive(Debug)]
+pub struct Inspection {
+    pub file_type: String,
+    pub contents: InspectedContents,
+}
+
+#[derive(Debug)]
+pub enum InspectedContents {
+    Archive(ArchiveContents),
+    /// Executable format and architecture, none for scripts
+    Executable(Option<String>),
+    /// Package metadata, or why it is not available (e.g. dpkg-deb is not installed)
+    Package(Result<String, String>),
+}
+
+fn package_metadata(name: &str, command: &mut Command) -> Result<String, String> {
+    command_output(name, command).map_err(|e| e.to_string())
 }
 
 fn file_info_from(name: &str, path: &Path) -> Result<FileInfo, InstallError> {
@@ -31,20 +139,78 @@ fn file_info_from(name: &str, path: &Path) -> Result<FileInfo, InstallError> {
     Ok(FileInfo::new(name, path))
 }
 
-fn find_installer_for(
-    file_type: &FileType,
-) -> fn(SupportedFileInfo, Destination, Vec<Executable>) -> InstallerResult {
+type ExtractFiles = fn(&Path, &Path) -> Result<(), InstallError>;
+
+struct Installer {
+    name: &'static str,
+    run: fn(SupportedFileInfo, Destination, Vec<Executable>) -> InstallerResult,
+    /// Archives are extracted before looking for the executables to install
+    extract: Option<ExtractFiles>,
+}
+
+impl Installer {
+    fn new(
+        name: &'static str,
+        run: fn(SupportedFileInfo, Destination, Vec<Executable>) -> InstallerResult,
+    ) -> Self {
+        Installer {
+            name,
+            run,
+            extract: None,
+        }
+    }
+
+    fn archive(
+        name: &'static str,
+        run: fn(SupportedFileInfo, Destination, Vec<Executable>) -> InstallerResult,
+        extract: ExtractFiles,
+    ) -> Self {
+        Installer {
+            name,
+            run,
+            extract: Some(extract),
+        }
+    }
+}
+
+fn find_installer_for(file_type: &FileType) -> Installer {
     match file_type {
-        FileType::Debian => DebianInstaller::run,
-        FileType::Rpm => RpmInstaller::run,
-        FileType::TarArchive(Compression::Gz) => TarArchiveInstaller::gz,
-        FileType::TarArchive(Compression::Xz) => TarArchiveInstaller::xz,
-        FileType::TarArchive(Compression::Bz2) => TarArchiveInstaller::bz2,
-        FileType::ZipArchive => ZipArchiveInstaller::run,
-        FileType::SevenZipArchive => SevenZipArchiveInstaller::run,
-        FileType::CompressedFile(Compression::Gz) => CompressedFileInstaller::gz,
-        FileType::CompressedFile(Compression::Xz) => CompressedFileInstaller::xz,
-        FileType::CompressedFile(Compression::Bz2) => CompressedFileInstaller::bz2,
-        FileType::ExecutableFile => ExecutableFileInstaller::run,
+        FileType::Debian => Installer::new("debian package (dpkg)", DebianInstaller::run),
+        FileType::Rpm => Installer::new("rpm package (rpm)", RpmInstaller::run),
+        FileType::TarArchive(Compression::Gz) => Installer::archive(
+            "tar archive (gz)",
+            TarArchiveInstaller::gz,
+            TarArchiveInstaller::extract_gz,
+        ),
+        FileType::TarArchive(Compression::Xz) => Installer::archive(
+            "tar archive (xz)",
+            TarArchiveInstaller::xz,
+            TarArchiveInstaller::extract_xz,
+        ),
+        FileType::TarArchive(Compression::Bz2) => Installer::archive(
+            "tar archive (bz2)",
+            TarArchiveInstaller::bz2,
+            TarArchiveInstaller::extract_bz2,
+        ),
+        FileType::ZipArchive => Installer::archive(
+            "zip archive",
+            ZipArchiveInstaller::run,
+            ZipArchiveInstaller::extract_archive,
+        ),
+        FileType::SevenZipArchive => Installer::archive(
+            "7z archive",
+            SevenZipArchiveInstaller::run,
+            SevenZipArchiveInstaller::extract_archive,
+        ),
+        FileType::CompressedFile(Compression::Gz) => {
+            Installer::new("compressed file (gz)", CompressedFileInstaller::gz)
+        }
+        FileType::CompressedFile(Compression::Xz) => {
+            Installer::new("compressed file (xz)", CompressedFileInstaller::xz)
+        }
+        FileType::CompressedFile(Compression::Bz2) => {
+            Installer::new("compressed file (bz2)", CompressedFileInstaller::bz2)
+        }
+        FileType::ExecutableFile => Installer::new("executable file", ExecutableFileInstaller::run),
     }
 }
@@ -1,4 +1,5 @@
-mod archive_installer;
+pub mod archive_installer;
+mod binary_format;
 mod command;
 mod compressed_file_installer;
 mod debian_installer;
@@ -14,4 +15,6 @@ mod seven_zip_archive_installer;
 mod tar_archive_installer;
 mod zip_archive_installer;
 
-pub use install::install;
+pub use install::{
+    InspectedContents, Inspection, InstallPlan, InstallTarget, inspect, install, plan,
+};
@@ -1,18 +1,27 @@
 use crate::installer::error::InstallError;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 #[derive(Debug)]
-pub struct InstallOutput(String);
+pub struct InstallOutput {
+    message: String,
+    files: Vec<PathBuf>,
+}
 
 impl InstallOutput {
-    pub fn new(message: String) -> Self {
-        Self(message)
+    pub fn new(message: String, files: Vec<PathBuf>) -> Self {
+        Self { message, files }
+    }
+
+    /// Files written by the installer (empty when a package manager installed the asset)
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
     }
 }
 
 impl Display for InstallOutput {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.message)
     }
 }
 
@@ -23,6 +23,11 @@ impl RpmInstaller {
                 .arg("--replacepkgs")
                 .arg(file_info.path),
         )
-        .map(|_| InstallOutput::new(format!("RPM package '{}' installed", file_info.name)))
+        .map(|_| {
+            InstallOutput::new(
+                format!("RPM package '{}' installed", file_info.name),
+                vec![],
+            )
+        })
     }
 }
@@ -21,7 +21,7 @@ impl SevenZipArchiveInstaller {
         ArchiveInstaller::run(Self::extract_archive, file_info, destination, executables)
     }
 
-    fn extract_archive(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+    pub fn extract_archive(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         exec_command(
             _7Z,
             Command::new(_7Z)
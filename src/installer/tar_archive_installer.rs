@@ -36,7 +36,7 @@ impl TarArchiveInstaller {
         ArchiveInstaller::run(Self::extract_bz2, file_info, destination, executables)
     }
 
-    fn extract_gz(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+    pub fn extract_gz(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         Self::extract_archive(
             |file| Box::new(flate2::read::GzDecoder::new(file)),
             source,
@@ -44,7 +44,7 @@ impl TarArchiveInstaller {
         )
     }
 
-    fn extract_xz(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+    pub fn extract_xz(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         Self::extract_archive(
             |file| Box::new(xz2::read::XzDecoder::new(file)),
             source,
@@ -52,7 +52,7 @@ impl TarArchiveInstaller {
         )
     }
 
-    fn extract_bz2(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+    pub fn extract_bz2(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         Self::extract_archive(
             |file| Box::new(bzip2::read::BzDecoder::new(file)),
             source,
@@ -19,7 +19,7 @@ impl ZipArchiveInstaller {
         ArchiveInstaller::run(Self::extract_archive, file_info, destination, executables)
     }
 
-    fn extract_archive(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
+    pub fn extract_archive(source: &Path, temp_dir: &Path) -> Result<(), InstallError> {
         let zip_archive = File::open(source).map_err(|x| {
             InstallError::Fatal(format!(
                 "Error opening zip archive {}: {}",
@@ -1,25 +1,44 @@
 // NOTE: this rule is not supported by rust-analyzer or JetBrains Rust plugin go to definition/refactoring tools so disable it until it's supported properly
 #![allow(clippy::uninlined_format_args)]
 
+use crate::cli::asset_pattern::AssetPattern;
 use crate::cli::color::Color;
 use crate::cli::completion_handler::CompletionHandler;
 use crate::cli::download_handler::DownloadHandler;
-use crate::cli::result::{HandlerError, HandlerResult};
+use crate::cli::inspect_handler::InspectHandler;
+use crate::cli::install_handler::InstallHandler;
+use crate::cli::list_handler::ListHandler;
+use crate::cli::match_handler::MatchHandler;
+use crate::cli::outdated_handler::OutdatedHandler;
+use crate::cli::output::{self, Event};
+use crate::cli::release_channel::LatestRelease;
+use crate::cli::release_filter::ReleaseFilter;
+use crate::cli::releases_handler::ReleasesHandler;
+use crate::cli::result::{ErrorKind, HandlerError, HandlerResult};
 use crate::cli::root_command::{Cli, Command};
+use crate::cli::sync_handler::SyncHandler;
+use crate::cli::uninstall_handler::UninstallHandler;
 use crate::cli::untag_handler::UntagHandler;
+use crate::cli::update_handler::UpdateHandler;
+use crate::github::client::ClientOptions;
 use clap::Parser;
+use std::io::IsTerminal;
 use std::process::exit;
 
+mod checksum;
 mod cli;
 mod env_var;
 mod github;
 mod installer;
+mod signature;
+mod state;
 mod system;
 mod temp_file;
 mod vector;
 
 fn main() {
     let cli: Cli = Cli::parse();
+    output::init(cli.output_format);
     init_ctrl_c_handler();
     handle(run(cli));
 }
@@ -30,39 +49,159 @@ fn init_ctrl_c_handler() {
     ctrlc::set_handler(move || {
         let term = dialoguer::console::Term::stderr();
         let _ = term.show_cursor();
-        exit(1);
+        exit(ErrorKind::Cancelled.exit_code());
     })
     .expect("Error initializing CTRL+C handler")
 }
 
 fn run(cli: Cli) -> HandlerResult {
+    let client_options = ClientOptions::new(
+        cli.retries,
+        cli.timeout,
+        cli.wait_for_rate_limit,
+        cli.github_api_url,
+    );
     match cli.cmd {
         Command::Download {
             repo,
             select,
+            select_regex,
+            exclude,
+            strict,
             automatic,
+            multiple,
             tag,
+            include_prereleases,
+            channel,
             output,
             install,
             install_file,
-        } => {
-            DownloadHandler::new(repo, select, automatic, tag, output, install, install_file).run()
+            verify,
+            checksum,
+            verify_signature,
+            jobs,
+            dry_run,
+        } => DownloadHandler::new(
+            repo,
+            select
+                .into_iter()
+                .map(AssetPattern::Wildcard)
+                .chain(select_regex.into_iter().map(AssetPattern::Regex))
+                .collect(),
+            automatic,
+            tag,
+            output,
+            install,
+            install_file,
+        )
+        .with_exclude(exclude)
+        .with_strict(strict)
+        .with_multiple(multiple)
+        .with_latest_release(LatestRelease::new(include_prereleases, channel))
+        .with_verification(verify, checksum)
+        .with_signature_key(verify_signature)
+        .with_jobs(jobs.get())
+        .with_dry_run(dry_run)
+        .with_client_options(client_options)
+        .run(),
+        Command::Match {
+            repo,
+            select,
+            select_regex,
+            exclude,
+            tag,
+            strict,
+        } => MatchHandler::new(
+            repo,
+            select
+                .into_iter()
+                .map(AssetPattern::Wildcard)
+                .chain(select_regex.into_iter().map(AssetPattern::Regex))
+                .collect(),
+            exclude,
+            tag,
+            strict,
+            client_options,
+        )
+        .run(),
+        Command::Inspect {
+            repo,
+            select,
+            select_regex,
+            tag,
+        } => InspectHandler::new(
+            repo,
+            select
+                .map(AssetPattern::Wildcard)
+                .or(select_regex.map(AssetPattern::Regex)),
+            tag,
+            client_options,
+        )
+        .run(),
+        Command::Install {
+            source,
+            output,
+            install_file,
+        } => InstallHandler::new(source, output, install_file, client_options).run(),
+        Command::Releases {
+            repo,
+            limit,
+            since,
+            prerelease,
+            no_prerelease,
+        } => ReleasesHandler::new(
+            repo,
+            limit,
+            ReleaseFilter::new(since, prerelease, no_prerelease),
+            client_options,
+        )
+        .run(),
+        Command::Untag {
+            repo,
+            wildcard_dates,
+        } => UntagHandler::new(repo, client_options)
+            .with_wildcard_dates(wildcard_dates)
+            .run(),
+        Command::Sync { manifest, jobs } => {
+            SyncHandler::new(manifest, jobs.get(), client_options).run()
         }
-        Command::Untag { repo } => UntagHandler::new(repo).run(),
+        Command::List => ListHandler::new().run(),
+        Command::Uninstall { repo } => UninstallHandler::new(repo).run(),
+        Command::Outdated => OutdatedHandler::new(client_options).run(),
+        Command::Update { repos } => UpdateHandler::new(repos, client_options).run(),
         Command::Completion { shell } => CompletionHandler::new(shell).run(),
     }
 }
 
 fn handle(result: HandlerResult) {
-    if let Err(error) = result {
-        match error {
-            HandlerError::Default(msg) => {
-                eprintln!("{}", Color::new(&msg).red().bold());
-                exit(1)
-            }
-            HandlerError::OperationCancelled(msg) => {
-                println!("Operation cancelled: {}", Color::new(&msg).bold());
-            }
+    let error = match result {
+        Ok(_) => return,
+        Err(error) => error,
+    };
+    let exit_code = exit_code(&error);
+    if output::is_json() {
+        output::emit(Event::Error {
+            kind: error.kind().as_str(),
+            message: error.to_string(),
+            exit_code,
+        });
+    } else if let HandlerError::OperationCancelled(msg) = &error {
+        println!("Operation cancelled: {}", Color::new(msg).bold());
+    } else {
+        eprintln!("{}", Color::new(&error.to_string()).red().bold());
+    }
+    exit(exit_code)
+}
+
+// NOTE: quitting an interactive prompt is not a failure, but scripts must be able to tell
+// that nothing was done
+fn exit_code(error: &HandlerError) -> i32 {
+    match error {
+        HandlerError::OperationCancelled(_)
+            if !output::is_json() && std::io::stdin().is_terminal() =>
+        {
+            0
         }
+        _ => error.kind().exit_code(),
     }
 }
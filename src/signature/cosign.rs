@@ -0,0 +1,46 @@
+use crate::signature::SignatureError;
+use std::path::Path;
+use std::process::Command;
+
+const COSIGN: &str = "cosign";
+
+/// Verify a blob signed with a cosign key pair, using either a bundle or a detached signature
+pub fn verify(key: &Path, file: &Path, signature: &Path) -> Result<(), SignatureError> {
+    let signature_flag = if is_bundle(signature) {
+        "--bundle"
+    } else {
+        "--signature"
+    };
+
+    let output = Command::new(COSIGN)
+        .arg("verify-blob")
+        .arg("--key")
+        .arg(key)
+        .arg(signature_flag)
+        .arg(signature)
+        .arg(file)
+        .output()
+        .map_err(|e| {
+            SignatureError(format!(
+                "An error occurred executing '{}' (is it installed and in your PATH?): {}",
+                COSIGN, e
+            ))
+        })?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(SignatureError(format!(
+            "Signature verification failed:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+fn is_bundle(signature: &Path) -> bool {
+    let name = signature
+        .file_name()
+        .map(|x| x.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".bundle") || name.ends_with(".sigstore.json") || name.ends_with(".sigstore")
+}
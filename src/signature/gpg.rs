@@ -0,0 +1,77 @@
+use crate::signature::SignatureError;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+const GPG: &str = "gpg";
+
+/// Verify a detached signature (ASCII armored or binary) with `gpg`.
+/// The public key is imported in a temporary GnuPG home, so the user keyring is never modified.
+pub fn verify(key: &Path, file: &Path, signature: &Path) -> Result<(), SignatureError> {
+    let gnupg_home = crate::temp_file::make_temp_dir().map_err(|e| {
+        SignatureError(format!(
+            "Error creating temporary GnuPG home directory: {}",
+            e
+        ))
+    })?;
+
+    let result = exec_gpg(&gnupg_home, &[OsStr::new("--import"), key.as_os_str()]).and_then(|_| {
+        exec_gpg(
+            &gnupg_home,
+            &[
+                OsStr::new("--verify"),
+                signature.as_os_str(),
+                file.as_os_str(),
+            ],
+        )
+    });
+
+    let _ = std::fs::remove_dir_all(&gnupg_home);
+    result
+}
+
+fn exec_gpg(gnupg_home: &Path, args: &[&OsStr]) -> Result<(), SignatureError> {
+    let output = Command::new(GPG)
+        .arg("--homedir")
+        .arg(gnupg_home)
+        .arg("--batch")
+        .args(args)
+        .output()
+        .map_err(|e| SignatureError(format!("An error occurred executing '{}': {}", GPG, e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(SignatureError(format!(
+            "Signature verification failed:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::signature::tests::fixture;
+
+    #[test]
+    fn valid_signature() {
+        let result = verify(
+            &fixture("gpg.asc"),
+            &fixture("hello.txt"),
+            &fixture("hello.txt.asc"),
+        );
+
+        assert!(result.is_ok(), "Expected Ok, got {:?}", result);
+    }
+
+    #[test]
+    fn tampered_file() {
+        let tampered = std::env::temp_dir().join("dra-gpg-tampered.txt");
+        std::fs::write(&tampered, "hello world!\n").unwrap();
+
+        let result = verify(&fixture("gpg.asc"), &tampered, &fixture("hello.txt.asc"));
+
+        assert!(result.is_err(), "Expected Err, got Ok");
+    }
+}
@@ -0,0 +1,82 @@
+use crate::signature::SignatureError;
+use minisign_verify::{PublicKey, Signature};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+pub fn verify(key: &Path, file: &Path, signature: &Path) -> Result<(), SignatureError> {
+    let public_key = PublicKey::from_file(key)
+        .map_err(|e| error(format!("Invalid minisign public key {}", key.display()), e))?;
+    let signature = Signature::from_file(signature).map_err(|e| {
+        error(
+            format!("Invalid minisign signature {}", signature.display()),
+            e,
+        )
+    })?;
+
+    let mut verifier = public_key
+        .verify_stream(&signature)
+        .map_err(|e| error("Signature verification failed".to_string(), e))?;
+    let mut source =
+        File::open(file).map_err(|e| error(format!("Error opening {}", file.display()), e))?;
+    let mut buffer = [0; 8192];
+    loop {
+        let bytes = source
+            .read(&mut buffer)
+            .map_err(|e| error(format!("Error reading {}", file.display()), e))?;
+        if bytes == 0 {
+            break;
+        }
+        verifier.update(&buffer[..bytes]);
+    }
+
+    verifier
+        .finalize()
+        .map_err(|e| error("Signature verification failed".to_string(), e))
+}
+
+fn error(message: String, error: impl std::fmt::Display) -> SignatureError {
+    SignatureError(format!("{}: {}", message, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::tests::fixture;
+
+    #[test]
+    fn valid_signature() {
+        let result = verify(
+            &fixture("minisign.pub"),
+            &fixture("hello.txt"),
+            &fixture("hello.txt.minisig"),
+        );
+
+        assert!(result.is_ok(), "Expected Ok, got {:?}", result);
+    }
+
+    #[test]
+    fn signed_with_another_key() {
+        let result = verify(
+            &fixture("other-minisign.pub"),
+            &fixture("hello.txt"),
+            &fixture("hello.txt.minisig"),
+        );
+
+        assert!(result.is_err(), "Expected Err, got Ok");
+    }
+
+    #[test]
+    fn tampered_file() {
+        let tampered = std::env::temp_dir().join("dra-minisign-tampered.txt");
+        std::fs::write(&tampered, "hello world!\n").unwrap();
+
+        let result = verify(
+            &fixture("minisign.pub"),
+            &tampered,
+            &fixture("hello.txt.minisig"),
+        );
+
+        assert!(result.is_err(), "Expected Err, got Ok");
+    }
+}
@@ -0,0 +1,267 @@
+use crate::github::release::Asset;
+use std::fmt::Formatter;
+use std::path::{Path, PathBuf};
+
+mod cosign;
+mod gpg;
+mod minisign;
+
+/// Public key (or keyring) used to verify the signature of an asset
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublicKey {
+    Minisign(PathBuf),
+    Gpg(PathBuf),
+    Cosign(PathBuf),
+}
+
+#[derive(Debug)]
+pub struct SignatureError(pub String);
+
+const MINISIGN_EXTENSIONS: [&str; 1] = [".minisig"];
+const GPG_EXTENSIONS: [&str; 3] = [".asc", ".sig", ".gpg"];
+const COSIGN_EXTENSIONS: [&str; 4] = [".bundle", ".sigstore.json", ".sigstore", ".sig"];
+
+const PGP_PUBLIC_KEY_HEADER: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----";
+const PGP_SIGNATURE_HEADER: &str = "-----BEGIN PGP SIGNATURE-----";
+// OpenPGP packet tags (RFC 4880, section 4.3)
+const PUBLIC_KEY_PACKET: u8 = 6;
+const SIGNATURE_PACKET: u8 = 2;
+
+impl PublicKey {
+    /// Detect the type of key from its content:
+    /// - minisign public keys (`minisign.pub`)
+    /// - cosign PEM encoded public keys (`cosign.pub`)
+    /// - GPG public keys, either ASCII armored or binary keyrings
+    pub fn try_from_file(src: &str) -> Result<PublicKey, String> {
+        let path = PathBuf::from(src);
+        let content = std::fs::read(&path)
+            .map_err(|e| format!("Error reading public key {}: {}", path.display(), e))?;
+        let first_line = first_line(&content);
+
+        if first_line.starts_with("untrusted comment:") || first_line.starts_with("RW") {
+            Ok(PublicKey::Minisign(path))
+        } else if first_line == "-----BEGIN PUBLIC KEY-----" {
+            Ok(PublicKey::Cosign(path))
+        } else if is_openpgp(&content, PGP_PUBLIC_KEY_HEADER, PUBLIC_KEY_PACKET) {
+            Ok(PublicKey::Gpg(path))
+        } else {
+            Err(format!(
+                "Unsupported public key {}: expected a minisign, cosign or GPG public key",
+                path.display()
+            ))
+        }
+    }
+
+    fn signature_extensions(&self) -> &[&str] {
+        match self {
+            PublicKey::Minisign(_) => &MINISIGN_EXTENSIONS,
+            PublicKey::Gpg(_) => &GPG_EXTENSIONS,
+            PublicKey::Cosign(_) => &COSIGN_EXTENSIONS,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            PublicKey::Minisign(path) | PublicKey::Gpg(path) | PublicKey::Cosign(path) => path,
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        match self {
+            PublicKey::Minisign(_) => "minisign",
+            PublicKey::Gpg(_) => "gpg",
+            PublicKey::Cosign(_) => "cosign",
+        }
+    }
+}
+
+/// Find the signature of an asset in the same release (e.g. `<asset>.minisig`, `<asset>.asc`, `<asset>.bundle`)
+pub fn find_signature_asset<'a>(
+    key: &PublicKey,
+    asset_name: &str,
+    assets: &'a [Asset],
+) -> Option<&'a Asset> {
+    key.signature_extensions().iter().find_map(|extension| {
+        let name = format!("{}{}", asset_name, extension).to_lowercase();
+        assets.iter().find(|x| x.name.to_lowercase() == name)
+    })
+}
+
+pub fn expected_signature_names(key: &PublicKey, asset_name: &str) -> Vec<String> {
+    key.signature_extensions()
+        .iter()
+        .map(|extension| format!("{}{}", asset_name, extension))
+        .collect()
+}
+
+pub fn verify(key: &PublicKey, file: &Path, signature: &Path) -> Result<(), SignatureError> {
+    check_signature_kind(key, signature)?;
+    match key {
+        PublicKey::Minisign(key) => minisign::verify(key, file, signature),
+        PublicKey::Gpg(key) => gpg::verify(key, file, signature),
+        PublicKey::Cosign(key) => cosign::verify(key, file, signature),
+    }
+}
+
+/// Both GPG and cosign publish `<asset>.sig` signatures, so it must be the same type of the public key
+fn check_signature_kind(key: &PublicKey, signature: &Path) -> Result<(), SignatureError> {
+    let expect_gpg = match key {
+        PublicKey::Gpg(_) => true,
+        PublicKey::Cosign(_) => false,
+        PublicKey::Minisign(_) => return Ok(()),
+    };
+    let content = std::fs::read(signature)
+        .map_err(|e| SignatureError(format!("Error reading signature: {}", e)))?;
+
+    if is_openpgp(&content, PGP_SIGNATURE_HEADER, SIGNATURE_PACKET) == expect_gpg {
+        Ok(())
+    } else {
+        Err(SignatureError(format!(
+            "The signature is not a {} signature",
+            key.kind()
+        )))
+    }
+}
+
+/// ASCII armored content starts with the given header, binary content with a packet of the given tag
+fn is_openpgp(content: &[u8], armor_header: &str, packet_tag: u8) -> bool {
+    first_line(content) == armor_header
+        || content.first().and_then(|x| openpgp_packet_tag(*x)) == Some(packet_tag)
+}
+
+/// Tag of an OpenPGP packet header, either in the new or old format (RFC 4880, section 4.2)
+fn openpgp_packet_tag(header: u8) -> Option<u8> {
+    if header & 0xC0 == 0xC0 {
+        Some(header & 0x3F)
+    } else if header & 0x80 == 0x80 {
+        Some((header & 0x3C) >> 2)
+    } else {
+        None
+    }
+}
+
+fn first_line(content: &[u8]) -> String {
+    String::from_utf8_lossy(content)
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    pub fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("signature")
+            .join(name)
+    }
+
+    #[test]
+    fn minisign_public_key() {
+        let path = fixture("minisign.pub");
+
+        let result = PublicKey::try_from_file(path.to_str().unwrap());
+
+        assert_eq!(Ok(PublicKey::Minisign(path)), result);
+    }
+
+    #[test]
+    fn gpg_public_key() {
+        let path = fixture("gpg.asc");
+
+        let result = PublicKey::try_from_file(path.to_str().unwrap());
+
+        assert_eq!(Ok(PublicKey::Gpg(path)), result);
+    }
+
+    #[test_case(&[0x99, 0x01, 0x0d, 0x04]; "old format")]
+    #[test_case(&[0xc6, 0x33, 0x04]; "new format")]
+    fn gpg_binary_public_key(content: &[u8]) {
+        let path = any_file(content);
+
+        let result = PublicKey::try_from_file(path.to_str().unwrap());
+
+        assert_eq!(Ok(PublicKey::Gpg(path)), result);
+    }
+
+    #[test]
+    fn unsupported_public_key() {
+        let path = any_file(b"ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAI any@example.com\n");
+
+        let result = PublicKey::try_from_file(path.to_str().unwrap());
+
+        assert!(result.is_err(), "Expected Err, got {:?}", result);
+    }
+
+    #[test]
+    fn missing_public_key() {
+        let result = PublicKey::try_from_file("/any/missing/key.pub");
+
+        assert!(result.is_err(), "Expected Err, got {:?}", result);
+    }
+
+    #[test_case(PublicKey::Minisign(PathBuf::new()), "tool.tar.gz.minisig"; "minisign")]
+    #[test_case(PublicKey::Gpg(PathBuf::new()), "tool.tar.gz.asc"; "gpg")]
+    #[test_case(PublicKey::Cosign(PathBuf::new()), "tool.tar.gz.bundle"; "cosign")]
+    fn signature_asset(key: PublicKey, expected: &str) {
+        let assets = vec![
+            asset("tool.tar.gz"),
+            asset("tool.tar.gz.bundle"),
+            asset("tool.tar.gz.asc"),
+            asset("tool.tar.gz.minisig"),
+            asset("other.tar.gz.minisig"),
+        ];
+
+        let result = find_signature_asset(&key, "tool.tar.gz", &assets);
+
+        assert_eq!(Some(expected), result.map(|x| x.name.as_str()));
+    }
+
+    #[test]
+    fn no_signature_asset() {
+        let assets = vec![asset("tool.tar.gz"), asset("other.tar.gz.minisig")];
+
+        let result =
+            find_signature_asset(&PublicKey::Minisign(PathBuf::new()), "tool.tar.gz", &assets);
+
+        assert!(result.is_none());
+    }
+
+    #[test_case(PublicKey::Gpg(PathBuf::new()), fixture("hello.txt.asc"), true; "gpg armored signature")]
+    #[test_case(PublicKey::Gpg(PathBuf::new()), any_file(&[0x89, 0x02, 0x33]), true; "gpg binary signature")]
+    #[test_case(PublicKey::Gpg(PathBuf::new()), any_file(b"MEUCIQDx\n"), false; "gpg with cosign signature")]
+    #[test_case(PublicKey::Cosign(PathBuf::new()), any_file(b"MEUCIQDx\n"), true; "cosign signature")]
+    #[test_case(PublicKey::Cosign(PathBuf::new()), fixture("hello.txt.asc"), false; "cosign with gpg signature")]
+    fn signature_kind(key: PublicKey, signature: PathBuf, expected: bool) {
+        let result = check_signature_kind(&key, &signature);
+
+        assert_eq!(expected, result.is_ok());
+    }
+
+    fn any_file(content: &[u8]) -> PathBuf {
+        let path = crate::temp_file::temp_file();
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn asset(name: &str) -> Asset {
+        Asset {
+            name: name.to_string(),
+            display_name: None,
+            download_url: "ANY_DOWNLOAD_URL".to_string(),
+            digest: None,
+        }
+    }
+}
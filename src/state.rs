@@ -0,0 +1,316 @@
+use crate::cli::release_channel::Channel;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE_NAME: &str = "installed.json";
+
+/// Tools installed by dra, persisted in the user data directory
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct InstallState {
+    #[serde(default)]
+    tools: Vec<InstalledTool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InstalledTool {
+    pub repository: String,
+    pub tag: String,
+    pub asset: String,
+    pub files: Vec<PathBuf>,
+    pub sha256: String,
+    /// RFC 3339 timestamp
+    pub installed_at: String,
+    pub selection: InstalledSelection,
+    /// Output path (file or directory) used when the tool was installed
+    pub destination: PathBuf,
+    pub install_files: Option<Vec<String>>,
+    /// Patterns of assets excluded from the selection
+    pub exclude: Option<Vec<String>>,
+    /// Semver requirement (e.g. `^1.4`) the installed release was selected with
+    #[serde(default)]
+    pub tag_requirement: Option<String>,
+    #[serde(default)]
+    pub include_prereleases: bool,
+    #[serde(default)]
+    pub channel: Option<Channel>,
+    #[serde(default)]
+    pub verification: InstalledVerification,
+    #[serde(default)]
+    pub signature: Option<InstalledSignature>,
+}
+
+/// How the asset was selected, so the same selection can be used on newer releases
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InstalledSelection {
+    Automatic,
+    Pattern(String),
+    Regex(String),
+}
+
+/// How the asset checksum was verified, so newer releases are verified the same way
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InstalledVerification {
+    #[default]
+    Disabled,
+    /// Checksum published in the release (`--verify`)
+    Release,
+    /// Checksum given with `--checksum`, which only matches the installed asset
+    Pinned,
+}
+
+/// Public key used to verify the asset signature
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InstalledSignature {
+    /// Absolute path of the public key
+    Key(PathBuf),
+    /// The public key path cannot be recorded (not valid UTF-8)
+    Unrecorded,
+}
+
+impl InstallState {
+    pub fn load(path: &Path) -> Result<InstallState, String> {
+        if !path.exists() {
+            return Ok(InstallState::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Error deserializing {}: {}", path.display(), e))
+    }
+
+    /// The state is written to a temporary file next to `path` and renamed over it,
+    /// so an interrupted `dra` never leaves a truncated state file
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        create_parent_dir(path)?;
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Error serializing install state: {}", e))?;
+        let temp_path = with_suffix(path, &format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&temp_path, content)
+            .and_then(|_| std::fs::rename(&temp_path, path))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                format!("Error writing {}: {}", path.display(), e)
+            })
+    }
+
+    /// Wait until no other `dra` process (or thread) changes the state stored in `path`.
+    /// The lock is released when dropped, hold it from `load` until `save`
+    pub fn lock(path: &Path) -> Result<StateLock, String> {
+        create_parent_dir(path)?;
+
+        let lock_path = with_suffix(path, ".lock");
+        let error = |e: std::io::Error| format!("Error locking {}: {}", lock_path.display(), e);
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(error)?;
+        file.lock().map_err(error)?;
+        Ok(StateLock { _file: file })
+    }
+
+    pub fn tools(&self) -> &[InstalledTool] {
+        &self.tools
+    }
+
+    pub fn find(&self, repository: &str) -> Option<&InstalledTool> {
+        self.tools.iter().find(|x| x.repository == repository)
+    }
+
+    /// Replace any previous installation of the same repository.
+    /// Files of the previous installation that are not overwritten are kept, so they can still be uninstalled.
+    pub fn record(&mut self, mut tool: InstalledTool) {
+        if let Some(previous) = self.remove(&tool.repository) {
+            let leftovers = previous
+                .files
+                .into_iter()
+                .filter(|x| !tool.files.contains(x))
+                .collect::<Vec<_>>();
+            tool.files.extend(leftovers);
+        }
+        self.tools.push(tool);
+        self.tools.sort_by(|a, b| a.repository.cmp(&b.repository));
+    }
+
+    pub fn remove(&mut self, repository: &str) -> Option<InstalledTool> {
+        let index = self.tools.iter().position(|x| x.repository == repository)?;
+        Some(self.tools.remove(index))
+    }
+}
+
+/// Exclusive lock of the install state, see [InstallState::lock]
+pub struct StateLock {
+    _file: File,
+}
+
+fn create_parent_dir(path: &Path) -> Result<(), String> {
+    match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Error creating {}: {}", parent.display(), e)),
+        None => Ok(()),
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_os_string();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Default location is `$XDG_DATA_HOME/dra/installed.json` (or the platform equivalent)
+pub fn state_file() -> Result<PathBuf, String> {
+    dirs::data_dir()
+        .map(|x| x.join("dra").join(STATE_FILE_NAME))
+        .ok_or_else(|| "Cannot determine user data directory".to_string())
+}
+
+pub fn now() -> String {
+    humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_new_tool() {
+        let mut state = InstallState::default();
+
+        state.record(tool("foo/bar", "v1.0.0", vec!["/bin/bar"]));
+
+        assert_eq!(
+            Some(&tool("foo/bar", "v1.0.0", vec!["/bin/bar"])),
+            state.find("foo/bar")
+        );
+    }
+
+    #[test]
+    fn record_replaces_previous_installation() {
+        let mut state = InstallState::default();
+        state.record(tool("foo/bar", "v1.0.0", vec!["/bin/bar", "/bin/bar-old"]));
+
+        state.record(tool("foo/bar", "v2.0.0", vec!["/bin/bar"]));
+
+        assert_eq!(1, state.tools().len());
+        assert_eq!(
+            Some(&tool("foo/bar", "v2.0.0", vec!["/bin/bar", "/bin/bar-old"])),
+            state.find("foo/bar")
+        );
+    }
+
+    #[test]
+    fn remove_tool() {
+        let mut state = InstallState::default();
+        state.record(tool("foo/bar", "v1.0.0", vec!["/bin/bar"]));
+        state.record(tool("foo/baz", "v1.0.0", vec!["/bin/baz"]));
+
+        let result = state.remove("foo/bar");
+
+        assert_eq!(Some(tool("foo/bar", "v1.0.0", vec!["/bin/bar"])), result);
+        assert_eq!(None, state.find("foo/bar"));
+        assert!(state.find("foo/baz").is_some());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = any_state_file();
+        let mut state = InstallState::default();
+        state.record(tool("foo/bar", "v1.0.0", vec!["/bin/bar"]));
+
+        state.save(&path).unwrap();
+        let result = InstallState::load(&path).unwrap();
+
+        assert_eq!(state, result);
+    }
+
+    #[test]
+    fn load_tool_installed_by_previous_versions() {
+        let path = any_state_file();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"{"tools":[{"repository":"foo/bar","tag":"v1.0.0","asset":"ANY_ASSET","files":["/bin/bar"],
+            "sha256":"ANY_SHA256","installed_at":"2026-01-01T00:00:00Z","selection":"automatic",
+            "destination":"/bin","install_files":null,"exclude":null}]}"#,
+        )
+        .unwrap();
+
+        let result = InstallState::load(&path).unwrap();
+
+        assert_eq!(
+            Some(&tool("foo/bar", "v1.0.0", vec!["/bin/bar"])),
+            result.find("foo/bar")
+        );
+    }
+
+    #[test]
+    fn save_replaces_previous_state() {
+        let path = any_state_file();
+        let mut state = InstallState::default();
+        state.record(tool("foo/bar", "v1.0.0", vec!["/bin/bar"]));
+        state.save(&path).unwrap();
+
+        InstallState::default().save(&path).unwrap();
+        let result = InstallState::load(&path).unwrap();
+
+        assert!(result.tools().is_empty());
+        let files = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(1, files);
+    }
+
+    #[test]
+    fn lock_is_exclusive() {
+        let path = any_state_file();
+
+        let _lock = InstallState::lock(&path).unwrap();
+        let other = File::options()
+            .write(true)
+            .open(with_suffix(&path, ".lock"))
+            .unwrap();
+
+        assert!(other.try_lock().is_err());
+    }
+
+    #[test]
+    fn load_missing_file() {
+        let path = std::env::temp_dir().join("dra-state-tests-missing.json");
+
+        let result = InstallState::load(&path).unwrap();
+
+        assert!(result.tools().is_empty());
+    }
+
+    fn any_state_file() -> PathBuf {
+        std::env::temp_dir()
+            .join("dra-state-tests")
+            .join(uuid::Uuid::new_v4().simple().to_string())
+            .join(STATE_FILE_NAME)
+    }
+
+    fn tool(repository: &str, tag: &str, files: Vec<&str>) -> InstalledTool {
+        InstalledTool {
+            repository: repository.to_string(),
+            tag: tag.to_string(),
+            asset: "ANY_ASSET".to_string(),
+            files: files.into_iter().map(PathBuf::from).collect(),
+            sha256: "ANY_SHA256".to_string(),
+            installed_at: "2026-01-01T00:00:00Z".to_string(),
+            selection: InstalledSelection::Automatic,
+            destination: PathBuf::from("/bin"),
+            install_files: None,
+            exclude: None,
+            tag_requirement: None,
+            include_prereleases: false,
+            channel: None,
+            verification: InstalledVerification::Disabled,
+            signature: None,
+        }
+    }
+}
@@ -88,6 +88,7 @@ mod acceptance_tests {
             name: name.into(),
             display_name: None,
             download_url: "ANY_DOWNLOAD_URL".into(),
+            digest: None,
         }
     }
 }
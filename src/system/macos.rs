@@ -184,6 +184,7 @@ mod tests {
             name: name.into(),
             display_name: None,
             download_url: "ANY_DOWNLOAD_URL".into(),
+            digest: None,
         }
     }
 }
@@ -169,6 +169,7 @@ mod tests {
             name: name.into(),
             display_name: None,
             download_url: "ANY_DOWNLOAD_URL".into(),
+            digest: None,
         }
     }
 
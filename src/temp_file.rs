@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 pub fn temp_file() -> PathBuf {
@@ -8,6 +9,16 @@ pub fn temp_file() -> PathBuf {
     temp_dir
 }
 
+/// Same path for the same key, so an interrupted download into it can be resumed
+pub fn temp_file_for(key: &str) -> PathBuf {
+    let hash = Sha256::digest(key.as_bytes())
+        .iter()
+        .take(16)
+        .map(|x| format!("{:02x}", x))
+        .collect::<String>();
+    std::env::temp_dir().join(format!("dra-{}", hash))
+}
+
 pub fn make_temp_dir() -> Result<PathBuf, std::io::Error> {
     let temp_dir = temp_file();
     std::fs::create_dir(&temp_dir)?;
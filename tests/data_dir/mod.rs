@@ -0,0 +1,17 @@
+// NOTE: this is needed because clippy gives false positives when compiling each integration test in different crates
+#![allow(dead_code)]
+
+use crate::mock_server::MockServer;
+use assert_cmd::Command;
+use assert_cmd::cargo_bin_cmd;
+use std::path::Path;
+
+/// `dra` command using the mock server and its own user data directory,
+/// so the tools it installs are recorded apart from the ones of the user
+pub fn dra(data_dir: &Path, server: &MockServer) -> Command {
+    let mut cmd = cargo_bin_cmd!("dra");
+    cmd.env("XDG_DATA_HOME", data_dir)
+        .env("DRA_GITHUB_API_URL", &server.url)
+        .env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true");
+    cmd
+}
@@ -1,6 +1,8 @@
 mod assertions;
+mod data_dir;
 mod docker;
 mod fs;
+mod mock_server;
 
 mod install {
     use test_case::test_case;
@@ -316,3 +318,708 @@ mod download {
         );
     }
 }
+
+mod github_api_url {
+    use crate::fs::{any_temp_file, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn download_from_custom_api() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", b"hello world\n")]);
+        let output_file = any_temp_file("hello.txt");
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .args(["-s", "hello.txt"])
+            .args(["-o", &path_to_string(output_file.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success();
+
+        assert_eq!(
+            "hello world\n",
+            std::fs::read_to_string(&output_file).unwrap()
+        );
+    }
+}
+
+mod exit_codes {
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn release_not_found() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[]);
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .arg("-a")
+            .arg("foo/baz")
+            .assert()
+            .code(4)
+            .stderr(predicates::str::contains("Repository or release not found"));
+    }
+
+    #[test]
+    fn no_asset_matched() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", b"hello world\n")]);
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .args(["-s", "missing.txt"])
+            .arg("foo/bar")
+            .assert()
+            .code(5)
+            .stderr(predicates::str::contains("No asset found for missing.txt"));
+    }
+}
+
+mod multiple_assets {
+    use crate::fs::{any_temp_dir, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn download_multiple_assets() {
+        let server = MockServer::start(
+            "foo/bar",
+            "1.0.0",
+            &[("hello.txt", b"hello\n"), ("world.txt", b"world\n")],
+        );
+        let output_dir = any_temp_dir();
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .args(["-s", "hello.txt", "-s", "world.txt"])
+            .args(["-o", &path_to_string(output_dir.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("Download summary"));
+
+        assert_eq!(
+            "hello\n",
+            std::fs::read_to_string(output_dir.join("hello.txt")).unwrap()
+        );
+        assert_eq!(
+            "world\n",
+            std::fs::read_to_string(output_dir.join("world.txt")).unwrap()
+        );
+    }
+}
+
+mod checksum {
+    use crate::fs::{any_temp_file, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn checksum_mismatch_keeps_existing_file() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", b"tampered\n")]);
+        let output_file = any_temp_file("hello.txt");
+        std::fs::write(&output_file, "hello world\n").unwrap();
+        let mut part_file = output_file.clone().into_os_string();
+        part_file.push(".part");
+        let wrong_checksum = "0".repeat(64);
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .args(["-s", "hello.txt"])
+            .args(["--checksum", &wrong_checksum])
+            .args(["-o", &path_to_string(output_file.clone())])
+            .arg("foo/bar")
+            .assert()
+            .code(3);
+
+        assert_eq!(
+            "hello world\n",
+            std::fs::read_to_string(&output_file).unwrap()
+        );
+        assert!(!std::path::Path::new(&part_file).exists());
+    }
+}
+
+mod resume {
+    use crate::fs::{any_temp_file, path_to_string};
+    use crate::mock_server::{MockServer, etag};
+    use assert_cmd::cargo_bin_cmd;
+    use std::ffi::OsString;
+    use std::path::{Path, PathBuf};
+
+    const CONTENT: &[u8] = b"hello world\n";
+
+    #[test]
+    fn resume_partial_download() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", CONTENT)]);
+        let output_file = any_temp_file("hello.txt");
+        let part_file = with_suffix(&output_file, ".part");
+        // NOTE: uppercase, to check that only the missing bytes are downloaded
+        std::fs::write(&part_file, "HELLO ").unwrap();
+        write_resume_info(&part_file, &server, &etag(CONTENT));
+
+        download(&server, &output_file);
+
+        assert_eq!(
+            "HELLO world\n",
+            std::fs::read_to_string(&output_file).unwrap()
+        );
+        assert!(!part_file.exists());
+        assert!(!with_suffix(&part_file, ".json").exists());
+    }
+
+    #[test]
+    fn changed_asset_starts_over() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", CONTENT)]);
+        let output_file = any_temp_file("hello.txt");
+        let part_file = with_suffix(&output_file, ".part");
+        std::fs::write(&part_file, "old bytes of another asset").unwrap();
+        write_resume_info(&part_file, &server, &etag(b"another asset"));
+
+        download(&server, &output_file);
+
+        assert_eq!(
+            "hello world\n",
+            std::fs::read_to_string(&output_file).unwrap()
+        );
+    }
+
+    #[test]
+    fn part_file_without_resume_info_starts_over() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", CONTENT)]);
+        let output_file = any_temp_file("hello.txt");
+        let part_file = with_suffix(&output_file, ".part");
+        std::fs::write(&part_file, "leftover ").unwrap();
+
+        download(&server, &output_file);
+
+        assert_eq!(
+            "hello world\n",
+            std::fs::read_to_string(&output_file).unwrap()
+        );
+    }
+
+    fn download(server: &MockServer, output_file: &Path) {
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_GITHUB_API_URL", &server.url)
+            .env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .arg("download")
+            .args(["-s", "hello.txt"])
+            .args(["-o", &path_to_string(output_file.to_path_buf())])
+            .arg("foo/bar")
+            .assert()
+            .success();
+    }
+
+    fn write_resume_info(part_file: &Path, server: &MockServer, validator: &str) {
+        let info = serde_json::json!({
+            "url": format!("{}/download/hello.txt", server.url),
+            "validator": validator,
+        });
+        std::fs::write(with_suffix(part_file, ".json"), info.to_string()).unwrap();
+    }
+
+    fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+        let mut path = OsString::from(path);
+        path.push(suffix);
+        PathBuf::from(path)
+    }
+}
+
+mod tag_requirement {
+    use crate::fs::{any_temp_file, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn download_highest_release_matching_requirement() {
+        let server = MockServer::start("foo/bar", "v1.2.0", &[("hello.txt", b"hello world\n")]);
+        let output_file = any_temp_file("hello.txt");
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .args(["--tag", "^1.1"])
+            .args(["-s", "hello.txt"])
+            .args(["-o", &path_to_string(output_file.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("Release tag is v1.2.0"));
+    }
+}
+
+mod json_output {
+    use crate::fs::{any_temp_file, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+    use predicates::prelude::*;
+
+    #[test]
+    fn download_with_json_output() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", b"hello world\n")]);
+        let output_file = any_temp_file("hello.txt");
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .args(["--output-format", "json"])
+            .arg("download")
+            .args(["-s", "hello.txt"])
+            .args(["-o", &path_to_string(output_file.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains(
+                r#"{"event":"release_resolved","repository":"foo/bar","tag":"1.0.0""#,
+            ))
+            .stdout(predicates::str::contains(
+                r#"{"event":"asset_selected","repository":"foo/bar","asset":"hello.txt""#,
+            ))
+            .stdout(predicates::str::contains(r#""event":"download_finished""#))
+            .stdout(predicates::str::contains(r#""size":12"#))
+            .stdout(predicates::str::contains("Release tag is").not());
+    }
+
+    #[test]
+    fn error_with_json_output() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[]);
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .args(["--output-format", "json"])
+            .arg("download")
+            .arg("foo/baz")
+            .assert()
+            .code(4)
+            .stdout(predicates::str::contains(
+                r#"{"event":"error","kind":"not_found""#,
+            ))
+            .stdout(predicates::str::contains(r#""exit_code":4"#));
+    }
+}
+
+mod dry_run {
+    use crate::fs::{any_temp_dir, any_temp_file, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn dry_run_does_not_download() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", b"hello world\n")]);
+        let output_file = any_temp_file("hello.txt");
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .arg("--dry-run")
+            .args(["-s", "hello.txt"])
+            .args(["-o", &path_to_string(output_file.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains(format!(
+                "Would download hello.txt to {}",
+                output_file.display()
+            )));
+
+        assert!(!output_file.exists());
+    }
+
+    #[test]
+    fn dry_run_install() {
+        let server =
+            MockServer::start("foo/bar", "1.0.0", &[("hello", b"#!/bin/sh\necho hello\n")]);
+        let output_dir = any_temp_dir();
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("download")
+            .args(["--dry-run", "--install"])
+            .args(["-s", "hello"])
+            .args(["-o", &path_to_string(output_dir.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("Installer: executable file"))
+            .stdout(predicates::str::contains(
+                output_dir.join("hello").display().to_string(),
+            ));
+
+        assert!(!output_dir.join("hello").exists());
+    }
+}
+
+mod inspect {
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn inspect_executable() {
+        let elf_header = b"\x7FELF\x02\x01\0\0\0\0\0\0\0\0\0\0\0\0\x3E\0";
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello", elf_header)]);
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .arg("inspect")
+            .args(["-s", "hello"])
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("executable file"))
+            .stdout(predicates::str::contains("ELF 64-bit x86_64"));
+    }
+}
+
+mod install_source {
+    use crate::fs::{any_temp_dir, path_to_string};
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn install_from_url() {
+        let server =
+            MockServer::start("foo/bar", "1.0.0", &[("hello", b"#!/bin/sh\necho hello\n")]);
+        let output_dir = any_temp_dir();
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .arg("install")
+            .args(["-o", &path_to_string(output_dir.clone())])
+            .arg(format!("{}/download/hello", server.url))
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("Installation completed!"));
+
+        assert!(output_dir.join("hello").exists());
+    }
+
+    #[test]
+    fn install_from_local_file() {
+        let source_dir = any_temp_dir();
+        let source = source_dir.join("hello");
+        std::fs::write(&source, b"#!/bin/sh\necho hello\n").unwrap();
+        let output_dir = any_temp_dir();
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.arg("install")
+            .args(["-o", &path_to_string(output_dir.join("my-hello"))])
+            .arg(path_to_string(source.clone()))
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("Installation completed!"));
+
+        assert!(output_dir.join("my-hello").exists());
+        assert!(source.exists());
+    }
+}
+
+mod releases {
+    use crate::mock_server::MockServer;
+    use assert_cmd::cargo_bin_cmd;
+
+    #[test]
+    fn list_releases() {
+        let server = MockServer::start("foo/bar", "1.0.0", &[("hello.txt", b"hello world\n")]);
+
+        let mut cmd = cargo_bin_cmd!("dra");
+
+        cmd.env("DRA_DISABLE_GITHUB_AUTHENTICATION", "true")
+            .args(["--github-api-url", &server.url])
+            .args(["--output-format", "json"])
+            .arg("releases")
+            .arg("foo/bar")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains(r#""tag":"1.0.0""#))
+            .stdout(predicates::str::contains(r#""hello.txt""#));
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod outdated {
+    use crate::data_dir::dra;
+    use crate::fs::{any_temp_dir, path_to_string};
+    use crate::mock_server::MockServer;
+    use std::path::Path;
+
+    #[test]
+    fn newer_release() {
+        let data_dir = any_temp_dir();
+        install(&data_dir, "v1.0.0");
+        let server = MockServer::start("foo/bar", "v1.1.0", &[("hello", b"hello v1.1.0\n")]);
+
+        dra(&data_dir, &server)
+            .arg("outdated")
+            .assert()
+            .success()
+            .stdout(predicates::str::is_match(r"foo/bar\s+v1\.0\.0\s+v1\.1\.0").unwrap());
+    }
+
+    #[test]
+    fn older_release() {
+        let data_dir = any_temp_dir();
+        install(&data_dir, "v1.1.0");
+        let server = MockServer::start("foo/bar", "v1.0.0", &[("hello", b"hello v1.0.0\n")]);
+
+        dra(&data_dir, &server)
+            .arg("outdated")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("All tools are up to date"));
+    }
+
+    fn install(data_dir: &Path, tag: &str) {
+        let server = MockServer::start("foo/bar", tag, &[("hello", b"hello\n")]);
+
+        dra(data_dir, &server)
+            .arg("download")
+            .arg("-i")
+            .args(["-s", "hello"])
+            .args(["-o", &path_to_string(any_temp_dir())])
+            .arg("foo/bar")
+            .assert()
+            .success();
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod update {
+    use crate::data_dir::dra;
+    use crate::fs::{any_temp_dir, path_to_string};
+    use crate::mock_server::MockServer;
+    use std::path::Path;
+
+    #[test]
+    fn update_to_newer_release() {
+        let data_dir = any_temp_dir();
+        let output_dir = any_temp_dir();
+        install(&data_dir, &output_dir, "v1.0.0");
+        let server = MockServer::start("foo/bar", "v1.1.0", &[("hello", b"hello v1.1.0\n")]);
+
+        dra(&data_dir, &server)
+            .arg("update")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains(
+                "Updating foo/bar from v1.0.0 to v1.1.0",
+            ));
+
+        assert_eq!(
+            "hello v1.1.0\n",
+            std::fs::read_to_string(output_dir.join("hello")).unwrap()
+        );
+        dra(&data_dir, &server)
+            .arg("list")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("foo/bar v1.1.0 (hello)"));
+    }
+
+    #[test]
+    fn never_update_to_older_release() {
+        let data_dir = any_temp_dir();
+        let output_dir = any_temp_dir();
+        install(&data_dir, &output_dir, "v1.1.0");
+        let server = MockServer::start("foo/bar", "v1.0.0", &[("hello", b"hello v1.0.0\n")]);
+
+        dra(&data_dir, &server)
+            .arg("update")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("foo/bar is up to date (v1.1.0)"));
+
+        assert_eq!(
+            "hello v1.1.0\n",
+            std::fs::read_to_string(output_dir.join("hello")).unwrap()
+        );
+    }
+
+    #[test]
+    fn tool_not_installed() {
+        let server = MockServer::start("foo/bar", "v1.0.0", &[("hello", b"hello v1.0.0\n")]);
+
+        dra(&any_temp_dir(), &server)
+            .arg("update")
+            .arg("foo/bar")
+            .assert()
+            .code(4);
+    }
+
+    fn install(data_dir: &Path, output_dir: &Path, tag: &str) {
+        let content = format!("hello {}\n", tag);
+        let server = MockServer::start("foo/bar", tag, &[("hello", content.as_bytes())]);
+
+        dra(data_dir, &server)
+            .arg("download")
+            .arg("-i")
+            .args(["-s", "hello"])
+            .args(["-o", &path_to_string(output_dir.to_path_buf())])
+            .arg("foo/bar")
+            .assert()
+            .success();
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod uninstall {
+    use crate::data_dir::dra;
+    use crate::fs::{any_temp_dir, path_to_string};
+    use crate::mock_server::MockServer;
+
+    #[test]
+    fn remove_installed_files() {
+        let data_dir = any_temp_dir();
+        let output_dir = any_temp_dir();
+        let server = MockServer::start("foo/bar", "v1.0.0", &[("hello", b"hello\n")]);
+        dra(&data_dir, &server)
+            .arg("download")
+            .arg("-i")
+            .args(["-s", "hello"])
+            .args(["-o", &path_to_string(output_dir.clone())])
+            .arg("foo/bar")
+            .assert()
+            .success();
+        assert!(output_dir.join("hello").exists());
+
+        dra(&data_dir, &server)
+            .arg("uninstall")
+            .arg("foo/bar")
+            .assert()
+            .success();
+
+        assert!(!output_dir.join("hello").exists());
+        dra(&data_dir, &server)
+            .arg("list")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("No tools installed with dra"));
+    }
+
+    #[test]
+    fn tool_not_installed() {
+        let server = MockServer::start("foo/bar", "v1.0.0", &[("hello", b"hello\n")]);
+
+        dra(&any_temp_dir(), &server)
+            .arg("uninstall")
+            .arg("foo/bar")
+            .assert()
+            .code(4);
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sync {
+    use crate::data_dir::dra;
+    use crate::fs::{any_temp_dir, any_temp_file, path_to_string};
+    use crate::mock_server::MockServer;
+
+    #[test]
+    fn install_manifest_tools() {
+        let data_dir = any_temp_dir();
+        let output_dir = any_temp_dir();
+        let server = MockServer::start(
+            "foo/bar",
+            "v1.0.0",
+            &[("hello", b"hello\n"), ("world.txt", b"world\n")],
+        );
+        let manifest = any_temp_file("dra.toml");
+        std::fs::write(
+            &manifest,
+            format!(
+                r#"
+[[tool]]
+repo = "foo/bar"
+select = "hello"
+install = true
+output = "{output}"
+
+[[tool]]
+repo = "foo/bar"
+select = "world.txt"
+output = "{output}/world.txt"
+"#,
+                output = path_to_string(output_dir.clone())
+            ),
+        )
+        .unwrap();
+
+        dra(&data_dir, &server)
+            .arg("sync")
+            .arg(path_to_string(manifest))
+            .assert()
+            .success();
+
+        assert!(output_dir.join("hello").exists());
+        assert_eq!(
+            "world\n",
+            std::fs::read_to_string(output_dir.join("world.txt")).unwrap()
+        );
+        dra(&data_dir, &server)
+            .arg("list")
+            .assert()
+            .success()
+            .stdout(predicates::str::contains("foo/bar v1.0.0 (hello)"));
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let data_dir = any_temp_dir();
+        let output_dir = any_temp_dir();
+        let server = MockServer::start("foo/bar", "v1.0.0", &[("hello", b"tampered\n")]);
+        let manifest = any_temp_file("dra.toml");
+        std::fs::write(
+            &manifest,
+            format!(
+                r#"
+[[tool]]
+repo = "foo/bar"
+select = "hello"
+install = true
+output = "{output}"
+checksum = "sha256:{checksum}"
+"#,
+                output = path_to_string(output_dir.clone()),
+                checksum = "0".repeat(64)
+            ),
+        )
+        .unwrap();
+
+        dra(&data_dir, &server)
+            .arg("sync")
+            .arg(path_to_string(manifest))
+            .assert()
+            .code(3);
+
+        assert!(!output_dir.join("hello").exists());
+    }
+}
@@ -0,0 +1,190 @@
+// NOTE: this rule is not supported by rust-analyzer or JetBrains Rust plugin go to definition/refactoring tools so disable it until it's supported properly
+#![allow(clippy::uninlined_format_args)]
+// NOTE: this is needed because clippy gives false positives when compiling each integration test in different crates
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+
+/// Minimal HTTP server that mimics the GitHub releases API for a single release,
+/// so tests can run without network access (see `--github-api-url`)
+pub struct MockServer {
+    pub url: String,
+}
+
+struct MockRelease {
+    repository: String,
+    tag: String,
+    assets: Vec<(String, Vec<u8>)>,
+}
+
+impl MockServer {
+    pub fn start(repository: &str, tag: &str, assets: &[(&str, &[u8])]) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let release = Arc::new(MockRelease {
+            repository: repository.to_string(),
+            tag: tag.to_string(),
+            assets: assets
+                .iter()
+                .map(|(name, content)| (name.to_string(), content.to_vec()))
+                .collect(),
+        });
+
+        let base_url = url.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = handle(stream, &base_url, &release);
+            }
+        });
+
+        MockServer { url }
+    }
+}
+
+fn handle(mut stream: TcpStream, base_url: &str, release: &MockRelease) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let path = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("/")
+        .to_string();
+
+    let mut range_start = None;
+    let mut if_range = None;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("range")
+        {
+            range_start = value
+                .trim()
+                .strip_prefix("bytes=")
+                .and_then(|x| x.strip_suffix('-'))
+                .and_then(|x| x.parse::<usize>().ok());
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("if-range")
+        {
+            if_range = Some(value.trim().to_string());
+        }
+    }
+
+    let release_paths = [
+        format!("/repos/{}/releases/latest", release.repository),
+        format!(
+            "/repos/{}/releases/tags/{}",
+            release.repository, release.tag
+        ),
+    ];
+    if release_paths.contains(&path) {
+        let body = release_json(base_url, release);
+        return respond(&mut stream, "200 OK", "application/json", body.as_bytes());
+    }
+
+    // NOTE: all releases fit in the first page
+    let releases_path = format!("/repos/{}/releases?", release.repository);
+    if let Some(query) = path.strip_prefix(&releases_path) {
+        let body = if query.split('&').any(|x| x == "page=1") {
+            format!("[{}]", release_json(base_url, release))
+        } else {
+            "[]".to_string()
+        };
+        return respond(&mut stream, "200 OK", "application/json", body.as_bytes());
+    }
+
+    let asset = path
+        .strip_prefix("/download/")
+        .and_then(|name| release.assets.iter().find(|(x, _)| x == name));
+    // NOTE: like real servers, the range is ignored when If-Range doesn't match the asset
+    let range_start = range_start.filter(|_| match (asset, &if_range) {
+        (Some((_, content)), Some(x)) => *x == etag(content),
+        _ => true,
+    });
+    match (asset, range_start) {
+        (Some((_, content)), Some(start)) if start < content.len() => respond_asset(
+            &mut stream,
+            "206 Partial Content",
+            &content[start..],
+            &etag(content),
+        ),
+        (Some((_, content)), _) => respond_asset(&mut stream, "200 OK", content, &etag(content)),
+        (None, _) => respond(
+            &mut stream,
+            "404 Not Found",
+            "application/json",
+            br#"{"message":"Not Found"}"#,
+        ),
+    }
+}
+
+fn release_json(base_url: &str, release: &MockRelease) -> String {
+    let assets = release
+        .assets
+        .iter()
+        .map(|(name, _)| {
+            format!(
+                r#"{{"name":"{name}","browser_download_url":"{base_url}/download/{name}","digest":null}}"#,
+                name = name,
+                base_url = base_url
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"tag_name":"{tag}","tarball_url":"{base_url}/tarball","zipball_url":"{base_url}/zipball","assets":[{assets}]}}"#,
+        tag = release.tag,
+        base_url = base_url,
+        assets = assets
+    )
+}
+
+/// Strong ETag of the asset content
+pub fn etag(content: &[u8]) -> String {
+    // NOTE: FNV-1a, only to give different contents a different ETag
+    let hash = content.iter().fold(0xcbf29ce484222325_u64, |hash, x| {
+        (hash ^ *x as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("\"{:x}\"", hash)
+}
+
+fn respond_asset(
+    stream: &mut TcpStream,
+    status: &str,
+    body: &[u8],
+    etag: &str,
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nETag: {}\r\nConnection: close\r\n\r\n",
+        status,
+        body.len(),
+        etag
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}